@@ -5,24 +5,74 @@ It uses a technique known as Brian Kernighan's algorithm, which efficiently clea
 significant set bit in each iteration.
 */
 
-pub fn count_set_bits(mut n: u32) -> u32 {
+use std::ops::{Add, BitAndAssign, Shl, Shr, Sub};
+
+/// The unsigned integer operations needed by the bit counting functions
+pub trait UnsignedInt:
+    Copy
+    + PartialEq
+    + BitAndAssign
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Shl<u32, Output = Self>
+    + Shr<u32, Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    /// The number of bits of the type, which is a power of two
+    const BITS: u32;
+}
+
+macro_rules! impl_unsigned_int {
+    ($($t:ty),*) => {
+        $(
+            impl UnsignedInt for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                const BITS: u32 = <$t>::BITS;
+            }
+        )*
+    };
+}
+
+impl_unsigned_int!(u8, u16, u32, u64, u128, usize);
+
+/// Counts the set bits of n, keeping the count in the type C. The count is
+/// at most the bit width of T, which fits even in a `u8`.
+fn kernighan<T: UnsignedInt, C: UnsignedInt>(mut n: T) -> C {
     // Initialize a variable to keep track of the count of set bits
-    let mut count = 0;
-    while n > 0 {
+    let mut count = C::ZERO;
+    while n != T::ZERO {
         // Clear the least significant set bit by
         // performing a bitwise AND operation with (n - 1)
-        n &= n - 1;
+        n &= n - T::ONE;
 
         // Increment the count for each set bit found
-        count += 1;
+        count = count + C::ONE;
     }
 
     count
 }
 
+pub fn count_set_bits(n: u32) -> u32 {
+    kernighan(n)
+}
+
+/// Counts the set bits of an unsigned integer of any width
+pub fn hamming_weight<T: UnsignedInt>(n: T) -> u32 {
+    kernighan(n)
+}
+
+/// Same as `hamming_weight`, but returns the count in the type of n rather
+/// than as a `u32`, so that generic code can keep computing in that type
+pub fn popcount<T: UnsignedInt>(n: T) -> T {
+    kernighan(n)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
 
     #[test]
     fn test_count_set_bits_zero() {
@@ -43,4 +93,38 @@ mod tests {
     fn test_count_set_bits_all_set_bits() {
         assert_eq!(count_set_bits(u32::MAX), 32); // Maximum value for u32, all set bits
     }
+
+    #[test]
+    fn test_hamming_weight_all_widths() {
+        assert_eq!(hamming_weight(0u8), 0);
+        assert_eq!(hamming_weight(u8::MAX), 8);
+        assert_eq!(hamming_weight(0b1010_0101u8), 4);
+        assert_eq!(hamming_weight(0u16), 0);
+        assert_eq!(hamming_weight(u16::MAX), 16);
+        assert_eq!(hamming_weight(0u32), 0);
+        assert_eq!(hamming_weight(u32::MAX), 32);
+        assert_eq!(hamming_weight(0u64), 0);
+        assert_eq!(hamming_weight(u64::MAX), 64);
+        assert_eq!(hamming_weight(1u64 << 63), 1);
+        assert_eq!(hamming_weight(0u128), 0);
+        assert_eq!(hamming_weight(u128::MAX), 128);
+        assert_eq!(hamming_weight(0usize), 0);
+        assert_eq!(hamming_weight(usize::MAX), usize::BITS);
+    }
+
+    #[test]
+    fn test_popcount_matches_count_ones() {
+        assert_eq!(popcount(0u8), 0);
+        assert_eq!(popcount(u8::MAX), 8u8);
+        assert_eq!(popcount(u128::MAX), 128u128);
+        for n in 0..=u16::MAX {
+            assert_eq!(popcount(n), n.count_ones() as u16);
+        }
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..1000 {
+            let n: u64 = rng.gen();
+            assert_eq!(popcount(n), n.count_ones() as u64);
+            assert_eq!(popcount(n as u32), (n as u32).count_ones());
+        }
+    }
 }
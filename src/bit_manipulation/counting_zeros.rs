@@ -0,0 +1,96 @@
+/*
+Counting leading and trailing zeros without the `leading_zeros` and
+`trailing_zeros` intrinsics. Both binary search for the highest (or lowest) set
+bit: at every step, if the upper (or lower) `step` bits of the remaining value
+are all zero, they are counted and shifted out. Starting from half the width
+and halving the step each time, this takes log2(width) steps.
+*/
+
+use super::UnsignedInt;
+
+/// Returns the number of zero bits above the highest set bit of n, which is
+/// the bit width of the type for 0
+pub fn count_leading_zeros<T: UnsignedInt>(mut n: T) -> u32 {
+    if n == T::ZERO {
+        return T::BITS;
+    }
+    let mut count = 0;
+    let mut step = T::BITS / 2;
+    while step > 0 {
+        if n >> (T::BITS - step) == T::ZERO {
+            count += step;
+            n = n << step;
+        }
+        step /= 2;
+    }
+    count
+}
+
+/// Returns the number of zero bits below the lowest set bit of n, which is
+/// the bit width of the type for 0
+pub fn count_trailing_zeros<T: UnsignedInt>(mut n: T) -> u32 {
+    if n == T::ZERO {
+        return T::BITS;
+    }
+    let mut count = 0;
+    let mut step = T::BITS / 2;
+    while step > 0 {
+        if n << (T::BITS - step) == T::ZERO {
+            count += step;
+            n = n >> step;
+        }
+        step /= 2;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn test_zero() {
+        assert_eq!(count_leading_zeros(0u8), 8);
+        assert_eq!(count_trailing_zeros(0u8), 8);
+        assert_eq!(count_leading_zeros(0u64), 64);
+        assert_eq!(count_trailing_zeros(0u128), 128);
+        assert_eq!(count_leading_zeros(0usize), usize::BITS);
+    }
+
+    #[test]
+    fn test_single_bits() {
+        for i in 0..128 {
+            let n = 1u128 << i;
+            assert_eq!(count_leading_zeros(n), 127 - i);
+            assert_eq!(count_trailing_zeros(n), i);
+        }
+        assert_eq!(count_leading_zeros(u32::MAX), 0);
+        assert_eq!(count_trailing_zeros(u32::MAX), 0);
+    }
+
+    #[test]
+    fn test_all_u16() {
+        for n in 0..=u16::MAX {
+            assert_eq!(count_leading_zeros(n), n.leading_zeros());
+            assert_eq!(count_trailing_zeros(n), n.trailing_zeros());
+        }
+    }
+
+    #[test]
+    fn test_random_values() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..1000 {
+            // shift out a random number of bits to get all zero counts
+            let n = rng.gen::<u64>() >> rng.gen_range(0..64);
+            assert_eq!(count_leading_zeros(n), n.leading_zeros());
+            assert_eq!(count_trailing_zeros(n), n.trailing_zeros());
+            let n = rng.gen::<u32>() << rng.gen_range(0..32);
+            assert_eq!(count_leading_zeros(n), n.leading_zeros());
+            assert_eq!(count_trailing_zeros(n), n.trailing_zeros());
+            let n = rng.gen::<u8>();
+            assert_eq!(count_leading_zeros(n), n.leading_zeros());
+            assert_eq!(count_trailing_zeros(n), n.trailing_zeros());
+        }
+    }
+}
@@ -1,7 +1,15 @@
 mod counting_bits;
+mod counting_zeros;
 mod highest_set_bit;
+mod power_of_two;
+mod power_set;
+mod reverse_bits;
 mod sum_of_two_integers;
 
-pub use counting_bits::count_set_bits;
+pub use counting_bits::{count_set_bits, hamming_weight, popcount, UnsignedInt};
+pub use counting_zeros::{count_leading_zeros, count_trailing_zeros};
 pub use highest_set_bit::find_highest_set_bit;
+pub use power_of_two::{is_power_of_two, next_power_of_two};
+pub use power_set::{power_set, subsets_of_size};
+pub use reverse_bits::reverse_bits;
 pub use sum_of_two_integers::add_two_integers;
@@ -0,0 +1,72 @@
+/*
+A number is a power of two when it has exactly one set bit. Clearing the lowest
+set bit with `n & (n - 1)`, the same trick used by Kernighan's bit counting,
+therefore leaves zero only for powers of two.
+*/
+
+pub fn is_power_of_two(n: u64) -> bool {
+    n != 0 && n & (n - 1) == 0
+}
+
+/// Returns the smallest power of two greater than or equal to n. Like the
+/// standard library, `next_power_of_two(0)` is 1.
+///
+/// Panics if the result does not fit in a `u64`.
+pub fn next_power_of_two(n: u64) -> u64 {
+    if n <= 1 {
+        return 1;
+    }
+    if n > 1 << 63 {
+        panic!("Next power of two does not fit in a u64");
+    }
+    // Smear the highest set bit of n - 1 into all lower positions, which
+    // leaves one less than the next power of two
+    let mut m = n - 1;
+    m |= m >> 1;
+    m |= m >> 2;
+    m |= m >> 4;
+    m |= m >> 8;
+    m |= m >> 16;
+    m |= m >> 32;
+    m + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_power_of_two() {
+        assert!(!is_power_of_two(0));
+        assert!(is_power_of_two(1));
+        assert!(is_power_of_two(2));
+        assert!(!is_power_of_two(3));
+        assert!(is_power_of_two(1 << 31));
+        assert!(!is_power_of_two((1 << 31) + 1));
+        assert!(!is_power_of_two((1 << 31) - 1));
+        assert!(is_power_of_two(1 << 63));
+        assert!(!is_power_of_two(u64::MAX));
+    }
+
+    #[test]
+    fn test_next_power_of_two() {
+        assert_eq!(next_power_of_two(0), 1);
+        assert_eq!(next_power_of_two(1), 1);
+        assert_eq!(next_power_of_two(2), 2);
+        assert_eq!(next_power_of_two(3), 4);
+        assert_eq!(next_power_of_two(5), 8);
+        assert_eq!(next_power_of_two(1 << 31), 1 << 31);
+        assert_eq!(next_power_of_two((1 << 31) - 1), 1 << 31);
+        assert_eq!(next_power_of_two((1 << 31) + 1), 1 << 32);
+        assert_eq!(next_power_of_two(1 << 63), 1 << 63);
+        for n in 0..1000 {
+            assert_eq!(next_power_of_two(n), n.next_power_of_two());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Next power of two does not fit in a u64")]
+    fn test_next_power_of_two_overflow() {
+        next_power_of_two((1 << 63) + 1);
+    }
+}
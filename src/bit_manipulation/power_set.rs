@@ -0,0 +1,111 @@
+/*
+The power set of n items holds all 2^n of their subsets. Numbering the items
+0 to n - 1, every subset corresponds to an n-bit mask whose bit i is set if
+item i belongs to the subset, so counting from 0 to 2^n - 1 enumerates every
+subset exactly once. The size of a subset is the number of set bits of its
+mask.
+
+The output grows exponentially: 20 items already give over a million
+subsets, and well before the 63 items that fit in a u64 mask there is not
+enough memory to hold them.
+*/
+
+use super::hamming_weight;
+
+/// Returns all subsets of items, each keeping the order of items. The
+/// subsets are ordered by their masks, so the empty set comes first and
+/// items itself comes last.
+///
+/// Panics if there are more than 63 items.
+pub fn power_set<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    masks(items.len()).map(|mask| subset(items, mask)).collect()
+}
+
+/// Returns the subsets of items with exactly k items, in the same order as
+/// `power_set`. This still checks all 2^n masks, counting the set bits of
+/// each with Kernighan's algorithm.
+///
+/// Panics if there are more than 63 items.
+pub fn subsets_of_size<T: Clone>(items: &[T], k: u32) -> Vec<Vec<T>> {
+    masks(items.len())
+        .filter(|&mask| hamming_weight(mask) == k)
+        .map(|mask| subset(items, mask))
+        .collect()
+}
+
+fn masks(n: usize) -> std::ops::Range<u64> {
+    if n > 63 {
+        panic!("Power set of more than 63 items is not supported");
+    }
+    0..1 << n
+}
+
+fn subset<T: Clone>(items: &[T], mask: u64) -> Vec<T> {
+    items
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| mask & (1 << i) != 0)
+        .map(|(_, item)| item.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_empty() {
+        let items: [i32; 0] = [];
+        assert_eq!(power_set(&items), vec![Vec::<i32>::new()]);
+        assert_eq!(subsets_of_size(&items, 0), vec![Vec::<i32>::new()]);
+        assert!(subsets_of_size(&items, 1).is_empty());
+    }
+
+    #[test]
+    fn test_three_items() {
+        assert_eq!(
+            power_set(&['a', 'b', 'c']),
+            vec![
+                vec![],
+                vec!['a'],
+                vec!['b'],
+                vec!['a', 'b'],
+                vec!['c'],
+                vec!['a', 'c'],
+                vec!['b', 'c'],
+                vec!['a', 'b', 'c'],
+            ]
+        );
+        assert_eq!(
+            subsets_of_size(&['a', 'b', 'c'], 2),
+            vec![vec!['a', 'b'], vec!['a', 'c'], vec!['b', 'c']]
+        );
+    }
+
+    #[test]
+    fn test_each_subset_once() {
+        let items: Vec<u32> = (0..10).collect();
+        let subsets = power_set(&items);
+        assert_eq!(subsets.len(), 1 << 10);
+        let unique: HashSet<&Vec<u32>> = subsets.iter().collect();
+        assert_eq!(unique.len(), subsets.len());
+        for subset in &subsets {
+            assert!(subset.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+
+        // the sizes follow the binomial coefficients
+        let binomial = [1, 10, 45, 120, 210, 252, 210, 120, 45, 10, 1];
+        for (k, &count) in binomial.iter().enumerate() {
+            let of_size = subsets_of_size(&items, k as u32);
+            assert_eq!(of_size.len(), count);
+            assert!(of_size.iter().all(|subset| subset.len() == k));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Power set of more than 63 items is not supported")]
+    fn test_too_many_items() {
+        power_set(&[0u8; 64]);
+    }
+}
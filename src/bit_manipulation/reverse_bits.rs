@@ -0,0 +1,70 @@
+/*
+Reverses the order of the lowest `width` bits of a number, so that bit 0 swaps
+places with bit `width - 1`, bit 1 with bit `width - 2`, and so on. This is the
+index permutation used by iterative FFT implementations.
+*/
+
+/// Reverses the lowest `width` bits of n. Bits of n at position `width` or
+/// higher are ignored, so they are zero in the result.
+///
+/// Panics if width is greater than 32.
+pub fn reverse_bits(mut n: u32, width: u32) -> u32 {
+    if width > 32 {
+        panic!("Width cannot be greater than 32");
+    }
+    let mut result = 0;
+    for _ in 0..width {
+        result = (result << 1) | (n & 1);
+        n >>= 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reverse_8_bits() {
+        assert_eq!(reverse_bits(0b0000_0001, 8), 0b1000_0000);
+        assert_eq!(reverse_bits(0b1101_0010, 8), 0b0100_1011);
+        assert_eq!(reverse_bits(0b1111_0000, 8), 0b0000_1111);
+        // Bits above the width are dropped
+        assert_eq!(reverse_bits(0b1_0000_0001, 8), 0b1000_0000);
+    }
+
+    #[test]
+    fn test_reverse_16_bits() {
+        assert_eq!(reverse_bits(0x0001, 16), 0x8000);
+        assert_eq!(reverse_bits(0x1234, 16), 0x2c48);
+        assert_eq!(reverse_bits(0xffff, 16), 0xffff);
+    }
+
+    #[test]
+    fn test_edge_widths() {
+        assert_eq!(reverse_bits(0xffff_ffff, 0), 0);
+        assert_eq!(reverse_bits(1, 32), 1 << 31);
+        assert_eq!(reverse_bits(0x1234_5678, 32), 0x1234_5678u32.reverse_bits());
+    }
+
+    #[test]
+    fn test_reverse_twice() {
+        for width in 0..=32 {
+            let mask = if width == 32 {
+                u32::MAX
+            } else {
+                (1 << width) - 1
+            };
+            for x in [0, 1, 0xa5a5_a5a5, 0x1234_5678, u32::MAX] {
+                let x = x & mask;
+                assert_eq!(reverse_bits(reverse_bits(x, width), width), x);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Width cannot be greater than 32")]
+    fn test_width_too_large() {
+        reverse_bits(1, 33);
+    }
+}
@@ -0,0 +1,52 @@
+//! Adler-32 checksum, as used by zlib (RFC 1950)
+//!
+//! It keeps two running sums modulo 65521, the largest prime below 2^16: `a`
+//! is one plus the sum of all bytes, and `b` is the sum of every value taken
+//! by `a`. Like CRC-32 it only detects accidental corruption.
+
+const MOD_ADLER: u32 = 65521;
+
+/// Number of bytes which can be summed before `b` could overflow a u32, so
+/// that the modulo only has to be taken once per chunk
+const CHUNK_BYTES: usize = 5552;
+
+pub fn adler32(data: &[u8]) -> u32 {
+    let mut a = 1u32;
+    let mut b = 0u32;
+    for chunk in data.chunks(CHUNK_BYTES) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        a %= MOD_ADLER;
+        b %= MOD_ADLER;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty() {
+        assert_eq!(adler32(b""), 1);
+    }
+
+    #[test]
+    fn ascii() {
+        assert_eq!(adler32(b"a"), 0x0062_0062);
+        assert_eq!(adler32(b"abc"), 0x024d_0127);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+        assert_eq!(
+            adler32(b"The quick brown fox jumps over the lazy dog"),
+            0x5bdc_0fda
+        );
+    }
+
+    #[test]
+    fn long_input() {
+        // Long enough for the sums to be reduced several times
+        assert_eq!(adler32(&[0xff; 100_000]), 0x149a_302c);
+    }
+}
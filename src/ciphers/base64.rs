@@ -3,8 +3,11 @@
     Written from scratch.
 */
 
-// The charset and padding used for en- and decoding.
+// The charsets and padding used for en- and decoding. The URL-safe charset of
+// RFC 4648 replaces `+` and `/`, which have a meaning in URLs and file names.
 const CHARSET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE_CHARSET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
 const PADDING: char = '=';
 
 /*
@@ -32,6 +35,15 @@ fn collect_six_bits(from: (u8, u8), offset: u8) -> u8 {
 }
 
 pub fn base64_encode(data: &[u8]) -> String {
+    encode_with_charset(data, CHARSET)
+}
+
+/// Same as `base64_encode`, but using the URL-safe charset
+pub fn base64_encode_url_safe(data: &[u8]) -> String {
+    encode_with_charset(data, URL_SAFE_CHARSET)
+}
+
+fn encode_with_charset(data: &[u8], charset: &[u8; 64]) -> String {
     let mut bits_encoded = 0usize;
     let mut encoded_string = String::new();
     // Using modulo twice to prevent an underflow, Wolfram|Alpha says this is optimal
@@ -49,7 +61,7 @@ pub fn base64_encode(data: &[u8]) -> String {
         };
         let bytes_to_encode = (lower_byte_to_encode, upper_byte_to_encode);
         let offset: u8 = (bits_encoded % 8) as u8;
-        encoded_string.push(CHARSET[collect_six_bits(bytes_to_encode, offset) as usize] as char);
+        encoded_string.push(charset[collect_six_bits(bytes_to_encode, offset) as usize] as char);
         bits_encoded += 6;
     }
     for _ in 0..padding_needed {
@@ -58,44 +70,84 @@ pub fn base64_encode(data: &[u8]) -> String {
     encoded_string
 }
 
+#[derive(Debug, Eq, PartialEq)]
+pub enum DecodeError {
+    /// The byte at this position is neither in the charset nor padding
+    InvalidCharacter(usize),
+    /// The padding is not at the end of the last group of 4 characters, or
+    /// does not match the number of characters before it
+    InvalidPadding,
+    /// The input is not padded and its length is not a multiple of 4
+    InvalidLength,
+}
+
 /*
-    Performs the exact inverse of the above description of `base64_encode`
+    Performs the exact inverse of the above description of `base64_encode`.
+    The input must be padded to a multiple of 4 characters.
 */
-pub fn base64_decode(data: &str) -> Result<Vec<u8>, (&str, u8)> {
+pub fn base64_decode(data: &str) -> Result<Vec<u8>, DecodeError> {
+    decode_with_charset(data, CHARSET)
+}
+
+/// Same as `base64_decode`, but using the URL-safe charset
+pub fn base64_decode_url_safe(data: &str) -> Result<Vec<u8>, DecodeError> {
+    decode_with_charset(data, URL_SAFE_CHARSET)
+}
+
+fn decode_with_charset(data: &str, charset: &[u8; 64]) -> Result<Vec<u8>, DecodeError> {
+    let data = data.as_bytes();
+    let data_len = data
+        .iter()
+        .position(|&byte| byte == PADDING as u8)
+        .unwrap_or(data.len());
     let mut collected_bits = 0;
     let mut byte_buffer = 0u16;
-    let mut databytes = data.bytes();
-    let mut outputbytes = Vec::<u8>::new();
-    'decodeloop: loop {
-        while collected_bits < 8 {
-            if let Some(nextbyte) = databytes.next() {
-                // Finds the first occurence of the latest byte
-                if let Some(idx) = CHARSET.iter().position(|&x| x == nextbyte) {
-                    byte_buffer |= ((idx & 0b00111111) as u16) << (10 - collected_bits);
-                    collected_bits += 6;
-                } else if nextbyte == (PADDING as u8) {
-                    collected_bits -= 2; // Padding only comes at the end so this works
-                } else {
-                    return Err((
-                        "Failed to decode base64: Expected byte from charset, found invalid byte.",
-                        nextbyte,
-                    ));
-                }
-            } else {
-                break 'decodeloop;
-            }
+    let mut outputbytes = Vec::<u8>::with_capacity(data_len * 3 / 4);
+    for (position, &nextbyte) in data[..data_len].iter().enumerate() {
+        // Finds the first occurence of the latest byte
+        let idx = charset
+            .iter()
+            .position(|&x| x == nextbyte)
+            .ok_or(DecodeError::InvalidCharacter(position))?;
+        byte_buffer |= (idx as u16) << (10 - collected_bits);
+        collected_bits += 6;
+        if collected_bits >= 8 {
+            outputbytes.push((byte_buffer >> 8) as u8);
+            byte_buffer <<= 8;
+            collected_bits -= 8;
         }
-        outputbytes.push(((0b1111111100000000 & byte_buffer) >> 8) as u8);
-        byte_buffer &= 0b0000000011111111;
-        byte_buffer <<= 8;
-        collected_bits -= 8;
-    }
-    if collected_bits != 0 {
-        return Err(("Failed to decode base64: Invalid padding.", collected_bits));
     }
+    // The fewer than 8 bits left over in byte_buffer are the ones the
+    // padding stands for, so they are dropped
+    check_padding(data, data_len)?;
     Ok(outputbytes)
 }
 
+/*
+    The padding fills up the last group of 4 characters: a group with 2 or 3
+    characters of data holds 1 or 2 bytes, followed by 2 or 1 padding
+    characters. A single character of data can't hold a byte, so there are
+    never more than 2 of them, and nothing may follow the padding.
+*/
+fn check_padding(data: &[u8], data_len: usize) -> Result<(), DecodeError> {
+    let padding = &data[data_len..];
+    if padding.is_empty() {
+        return if data_len.is_multiple_of(4) {
+            Ok(())
+        } else {
+            Err(DecodeError::InvalidLength)
+        };
+    }
+    if data_len == 0
+        || padding.len() > 2
+        || !data.len().is_multiple_of(4)
+        || padding.iter().any(|&byte| byte != PADDING as u8)
+    {
+        return Err(DecodeError::InvalidPadding);
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -269,4 +321,104 @@ mod tests {
         test_d_e!("SGFwcHkgSGFja3RvYmVyZmVzdCE=");
         test_d_e!("PVRoZSBBbGdvcml0aG1zPQ==");
     }
+
+    #[test]
+    fn padding_boundaries() {
+        // 0, 1 and 2 bytes left over after the last full group of 3
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foob"), "Zm9vYg==");
+        assert_eq!(base64_encode(b"fooba"), "Zm9vYmE=");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+        for text in ["", "f", "fo", "foo", "foob", "fooba", "foobar"] {
+            assert_eq!(
+                base64_decode(&base64_encode(text.as_bytes())).unwrap(),
+                text.as_bytes()
+            );
+        }
+    }
+
+    #[test]
+    fn url_safe() {
+        let data = b"\xfb\xff\xbf\xfb\xef";
+        assert_eq!(base64_encode(data), "+/+/++8=");
+        assert_eq!(base64_encode_url_safe(data), "-_-_--8=");
+        assert_eq!(base64_decode_url_safe("-_-_--8=").unwrap(), data);
+        assert_eq!(
+            base64_decode_url_safe(&base64_encode_url_safe(b"Lorem Ipsum sit dolor amet."))
+                .unwrap(),
+            b"Lorem Ipsum sit dolor amet."
+        );
+        // Each decoder only accepts its own charset
+        assert!(base64_decode("-_-_--8=").is_err());
+        assert!(base64_decode_url_safe("+/+/++8=").is_err());
+    }
+
+    #[test]
+    fn invalid_characters() {
+        assert_eq!(
+            base64_decode("Zm9v!A=="),
+            Err(DecodeError::InvalidCharacter(4))
+        );
+        assert_eq!(
+            base64_decode("Zm 9v"),
+            Err(DecodeError::InvalidCharacter(2))
+        );
+        // The position counts bytes, not characters
+        assert_eq!(base64_decode("Zé9v"), Err(DecodeError::InvalidCharacter(1)));
+    }
+
+    #[test]
+    fn invalid_padding() {
+        for data in [
+            "=", "==", "===", "====", "=Zm9", "==Zm", "A===", "AB=C", "Zm9vYg=", "Zm9v=",
+        ] {
+            assert_eq!(
+                base64_decode(data),
+                Err(DecodeError::InvalidPadding),
+                "{data}"
+            );
+        }
+        assert_eq!(
+            base64_decode("Zm9vYg==Zm9v"),
+            Err(DecodeError::InvalidPadding)
+        );
+        assert_eq!(
+            base64_decode_url_safe("="),
+            Err(DecodeError::InvalidPadding)
+        );
+    }
+
+    #[test]
+    fn invalid_length() {
+        for data in ["A", "Zm9", "Zm9vY", "Zm9vYg"] {
+            assert_eq!(
+                base64_decode(data),
+                Err(DecodeError::InvalidLength),
+                "{data}"
+            );
+        }
+        assert_eq!(base64_decode(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn never_panics() {
+        // Every string of up to 8 characters from a small alphabet
+        let alphabet = ['A', '/', '=', '!'];
+        let mut strings = vec![String::new()];
+        for _ in 0..8 {
+            strings = strings
+                .iter()
+                .flat_map(|s| alphabet.iter().map(move |&c| format!("{s}{c}")))
+                .collect();
+            for data in &strings {
+                if let Ok(decoded) = base64_decode(data) {
+                    let padding = data.matches(PADDING).count();
+                    assert_eq!(decoded.len(), data.len() / 4 * 3 - padding, "{data}");
+                }
+            }
+        }
+    }
 }
@@ -205,8 +205,121 @@ pub fn blake2b(m: &[u8], k: &[u8], nn: u8) -> Vec<u8> {
     blake2(blocks, m.len() as u128, kk as u64, nn as Word)
 }
 
+/// Incremental BLAKE2b with a DIGEST_BYTES long output (at most 64 bytes),
+/// optionally keyed. A keyed BLAKE2b is a MAC on its own, so it does not need
+/// to be wrapped in HMAC.
+#[derive(Clone)]
+pub struct Blake2b<const DIGEST_BYTES: usize> {
+    h: [Word; 8],
+    /// The bytes not compressed yet. The last block has to be compressed with
+    /// the final flag, so a full buffer is only compressed once more data
+    /// comes in.
+    buffer: [u8; BB],
+    buffer_len: usize,
+    /// Number of bytes compressed so far
+    t: u128,
+    finalized: bool,
+}
+
+impl<const DIGEST_BYTES: usize> Blake2b<DIGEST_BYTES> {
+    pub fn new_default() -> Self {
+        Self::with_key_len(0)
+    }
+
+    /// Creates a hasher computing the MAC of the message under key, which can
+    /// be at most 64 bytes long
+    pub fn new_keyed(key: &[u8]) -> Result<Self, &'static str> {
+        if key.len() > KK_MAX {
+            return Err("Key must be at most 64 bytes long");
+        }
+        let mut hasher = Self::with_key_len(key.len());
+        if !key.is_empty() {
+            // The key is padded to a full block, which is hashed first
+            hasher.buffer[..key.len()].copy_from_slice(key);
+            hasher.buffer_len = BB;
+        }
+        Ok(hasher)
+    }
+
+    fn with_key_len(kk: usize) -> Self {
+        assert!(
+            0 < DIGEST_BYTES && DIGEST_BYTES <= NN_MAX as usize,
+            "Digest length must be between 1 and 64 bytes"
+        );
+        let mut h = IV;
+        h[0] ^= 0x01010000u64 ^ ((kk as Word) << 8) ^ DIGEST_BYTES as Word;
+        Self {
+            h,
+            buffer: [0; BB],
+            buffer_len: 0,
+            t: 0,
+            finalized: false,
+        }
+    }
+
+    pub fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            if self.buffer_len == BB {
+                self.t += BB as u128;
+                let block = self.buffer_block();
+                f(&mut self.h, block, self.t, false);
+                self.buffer_len = 0;
+            }
+            let n = min(data.len(), BB - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + n].copy_from_slice(&data[..n]);
+            self.buffer_len += n;
+            data = &data[n..];
+        }
+    }
+
+    pub fn get_hash(&mut self) -> [u8; DIGEST_BYTES] {
+        if !self.finalized {
+            self.finalized = true;
+            self.buffer[self.buffer_len..].fill(0);
+            self.t += self.buffer_len as u128;
+            let block = self.buffer_block();
+            f(&mut self.h, block, self.t, true);
+        }
+        let mut result = [0u8; DIGEST_BYTES];
+        for (r, b) in result
+            .iter_mut()
+            .zip(self.h.iter().flat_map(|n| n.to_le_bytes()))
+        {
+            *r = b;
+        }
+        result
+    }
+
+    fn buffer_block(&self) -> Block {
+        let mut block = blank_block();
+        for (w, c) in block.iter_mut().zip(self.buffer.chunks(U64BYTES)) {
+            *w = bytes_to_word(c);
+        }
+        block
+    }
+}
+
+impl<const DIGEST_BYTES: usize> super::BlockHasher<DIGEST_BYTES> for Blake2b<DIGEST_BYTES> {
+    const BLOCK_BYTES: usize = BB;
+}
+
+impl<const DIGEST_BYTES: usize> super::Hasher<DIGEST_BYTES> for Blake2b<DIGEST_BYTES> {
+    fn new_default() -> Self {
+        Blake2b::new_default()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.update(data);
+    }
+
+    fn get_hash(&mut self) -> [u8; DIGEST_BYTES] {
+        self.get_hash()
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use super::super::sha256::tests::get_hash_string;
     use super::*;
 
     macro_rules! digest_test {
@@ -315,4 +428,74 @@ mod test {
             0x83, 0xe1, 0xdc, 0xdc, 0xcf, 0x6e, 0x30, 0x22
         ]
     );
+
+    fn test_key() -> Vec<u8> {
+        (0..64).collect()
+    }
+
+    #[test]
+    fn blake2b_hasher_matches_rfc_vectors() {
+        let mut hasher = Blake2b::<64>::new_default();
+        hasher.update(b"abc");
+        assert_eq!(hasher.get_hash().to_vec(), blake2b(b"abc", &[], 64));
+        // Finalization is not repeated
+        assert_eq!(hasher.get_hash().to_vec(), blake2b(b"abc", &[], 64));
+
+        let mut hasher = Blake2b::<64>::new_default();
+        assert_eq!(hasher.get_hash().to_vec(), blake2b(&[], &[], 64));
+
+        let mut hasher = Blake2b::<64>::new_default();
+        hasher.update(b"The quick brown fox jumps over the lazy dog");
+        assert_eq!(
+            get_hash_string(&hasher.get_hash()),
+            "a8add4bdddfd93e4877d2746e62817b116364a1fa7bc148d95090bc7333b3673\
+             f82401cf7aa2e4cb1ecd90296e3f14cb5413f8ed77be73045b13914cdcd6a918"
+        );
+    }
+
+    #[test]
+    fn blake2b_hasher_short_digest() {
+        let mut hasher = Blake2b::<32>::new_default();
+        hasher.update(b"abc");
+        assert_eq!(
+            get_hash_string(&hasher.get_hash()),
+            "bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319"
+        );
+    }
+
+    #[test]
+    fn blake2b_hasher_keyed() {
+        let key = test_key();
+        let mut hasher = Blake2b::<64>::new_keyed(&key).unwrap();
+        assert_eq!(hasher.get_hash().to_vec(), blake2b(&[], &key, 64));
+
+        let message: Vec<u8> = (0..=255).collect();
+        let mut hasher = Blake2b::<64>::new_keyed(&key).unwrap();
+        hasher.update(&message);
+        assert_eq!(
+            get_hash_string(&hasher.get_hash()),
+            "b72071e096277edebb8ee5134dd3714996307ba3a55aa4733d412abbe28e909e\
+             10e57e6fbfb4ef53b3b960518294ff889a90829254412e2a60b85add07a3674f"
+        );
+
+        assert!(Blake2b::<64>::new_keyed(&[0; 65]).is_err());
+    }
+
+    #[test]
+    fn blake2b_hasher_chunked_updates() {
+        // Messages around the block size, fed in uneven chunks
+        let message: Vec<u8> = (0..400).map(|i| (i * 7) as u8).collect();
+        for len in [0, 1, 127, 128, 129, 255, 256, 257, 400] {
+            for key in [vec![], test_key()] {
+                let mut hasher = Blake2b::<64>::new_keyed(&key).unwrap();
+                for chunk in message[..len].chunks(33) {
+                    hasher.update(chunk);
+                }
+                assert_eq!(
+                    hasher.get_hash().to_vec(),
+                    blake2b(&message[..len], &key, 64)
+                );
+            }
+        }
+    }
 }
@@ -7,7 +7,9 @@
 //! 'n'. This implementation does not rotate unicode characters.
 
 /// Caesar cipher to rotate cipher text by shift and return an owned String.
+/// Shifts of 26 or more wrap around, so a shift of 26 leaves the text as is.
 pub fn caesar(cipher: &str, shift: u8) -> String {
+    let shift = shift % 26;
     cipher
         .chars()
         .map(|c| {
@@ -22,6 +24,17 @@ pub fn caesar(cipher: &str, shift: u8) -> String {
         .collect()
 }
 
+/// Encrypts text by rotating each ascii letter forward by shift, preserving
+/// case and leaving every other character unchanged.
+pub fn caesar_encrypt(text: &str, shift: u8) -> String {
+    caesar(text, shift)
+}
+
+/// Reverses `caesar_encrypt` with the same shift.
+pub fn caesar_decrypt(text: &str, shift: u8) -> String {
+    caesar(text, 26 - shift % 26)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -40,4 +53,25 @@ mod tests {
     fn caesar_unicode() {
         assert_eq!(caesar("attack at dawn 攻", 5), "fyyfhp fy ifbs 攻");
     }
+
+    #[test]
+    fn caesar_wrap_around() {
+        assert_eq!(caesar_encrypt("z", 1), "a");
+        assert_eq!(caesar_encrypt("Z", 1), "A");
+        assert_eq!(caesar_encrypt("Hello, World!", 26), "Hello, World!");
+        assert_eq!(caesar_encrypt("Hello, World!", 0), "Hello, World!");
+        assert_eq!(caesar_encrypt("xyz", 29), "abc");
+        assert_eq!(caesar_encrypt("xyz", 255), caesar_encrypt("xyz", 255 % 26));
+    }
+
+    #[test]
+    fn caesar_mixed_case_round_trip() {
+        let plain = "The Quick Brown Fox, 42 times!";
+        let encrypted = caesar_encrypt(plain, 3);
+        assert_eq!(encrypted, "Wkh Txlfn Eurzq Ira, 42 wlphv!");
+        assert_eq!(caesar_decrypt(&encrypted, 3), plain);
+        for shift in [0, 1, 13, 25, 26, 27, 200, 255] {
+            assert_eq!(caesar_decrypt(&caesar_encrypt(plain, shift), shift), plain);
+        }
+    }
 }
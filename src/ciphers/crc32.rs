@@ -0,0 +1,62 @@
+//! CRC-32 checksum, as used by zip, gzip and PNG
+//!
+//! This is a checksum for detecting accidental corruption, not a cryptographic
+//! hash: it is trivial to craft data with a given CRC.
+
+/// The IEEE 802.3 polynomial, in reversed bit order
+const POLYNOMIAL: u32 = 0xedb8_8320;
+
+/// CRC of every byte value, so that data can be processed a byte at a time
+const TABLE: [u32; 256] = make_table();
+
+const fn make_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut byte = 0;
+    while byte < 256 {
+        let mut crc = byte as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ POLYNOMIAL
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[byte] = crc;
+        byte += 1;
+    }
+    table
+}
+
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc = (crc >> 8) ^ TABLE[((crc ^ byte as u32) & 0xff) as usize];
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_value() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn ascii() {
+        assert_eq!(
+            crc32(b"The quick brown fox jumps over the lazy dog"),
+            0x414f_a339
+        );
+        assert_eq!(crc32(b"a"), 0xe8b7_be43);
+    }
+}
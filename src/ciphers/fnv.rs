@@ -0,0 +1,127 @@
+//! FNV-1a, the Fowler–Noll–Vo hash function
+//!
+//! For every byte, FNV-1a xors it into the state and then multiplies the
+//! state by the FNV prime. It is fast and spreads short keys well, which makes
+//! it a good fit for hash tables, but it is not a cryptographic hash.
+
+const OFFSET_BASIS_32: u32 = 0x811c_9dc5;
+const PRIME_32: u32 = 0x0100_0193;
+const OFFSET_BASIS_64: u64 = 0xcbf2_9ce4_8422_2325;
+const PRIME_64: u64 = 0x0000_0100_0000_01b3;
+
+/// The 32-bit variant of FNV-1a. The digest is the big-endian hash value.
+#[derive(Clone)]
+pub struct Fnv1a32 {
+    hash: u32,
+}
+
+impl Fnv1a32 {
+    pub fn new_default() -> Self {
+        Fnv1a32 {
+            hash: OFFSET_BASIS_32,
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.hash = (self.hash ^ byte as u32).wrapping_mul(PRIME_32);
+        }
+    }
+
+    pub fn get_hash(&mut self) -> [u8; 4] {
+        self.hash.to_be_bytes()
+    }
+}
+
+impl super::Hasher<4> for Fnv1a32 {
+    fn new_default() -> Self {
+        Fnv1a32::new_default()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.update(data);
+    }
+
+    fn get_hash(&mut self) -> [u8; 4] {
+        self.get_hash()
+    }
+}
+
+/// The 64-bit variant of FNV-1a. The digest is the big-endian hash value.
+#[derive(Clone)]
+pub struct Fnv1a64 {
+    hash: u64,
+}
+
+impl Fnv1a64 {
+    pub fn new_default() -> Self {
+        Fnv1a64 {
+            hash: OFFSET_BASIS_64,
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.hash = (self.hash ^ byte as u64).wrapping_mul(PRIME_64);
+        }
+    }
+
+    pub fn get_hash(&mut self) -> [u8; 8] {
+        self.hash.to_be_bytes()
+    }
+}
+
+impl super::Hasher<8> for Fnv1a64 {
+    fn new_default() -> Self {
+        Fnv1a64::new_default()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.update(data);
+    }
+
+    fn get_hash(&mut self) -> [u8; 8] {
+        self.get_hash()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fnv1a32(data: &[u8]) -> u32 {
+        let mut hasher = Fnv1a32::new_default();
+        hasher.update(data);
+        u32::from_be_bytes(hasher.get_hash())
+    }
+
+    fn fnv1a64(data: &[u8]) -> u64 {
+        let mut hasher = Fnv1a64::new_default();
+        hasher.update(data);
+        u64::from_be_bytes(hasher.get_hash())
+    }
+
+    #[test]
+    fn fnv1a32_vectors() {
+        assert_eq!(fnv1a32(b""), 0x811c_9dc5);
+        assert_eq!(fnv1a32(b"a"), 0xe40c_292c);
+        assert_eq!(fnv1a32(b"foo"), 0xa9f3_7ed7);
+        assert_eq!(fnv1a32(b"foobar"), 0xbf9c_f968);
+    }
+
+    #[test]
+    fn fnv1a64_vectors() {
+        assert_eq!(fnv1a64(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a64(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a64(b"foo"), 0xdcb2_7518_fed9_d577);
+        assert_eq!(fnv1a64(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn incremental() {
+        let mut hasher = Fnv1a64::new_default();
+        hasher.update(b"foo");
+        hasher.update(b"bar");
+        assert_eq!(u64::from_be_bytes(hasher.get_hash()), fnv1a64(b"foobar"));
+    }
+}
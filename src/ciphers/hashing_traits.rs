@@ -1,16 +1,187 @@
+use super::{Blake2b, Fnv1a32, Fnv1a64, MD5, SHA1, SHA224, SHA256, SHA512};
+use std::io::{self, Read};
+
+/// An error of a hash function which can fail while hashing
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum HashError {
+    /// The message is longer than the hash function can process
+    InputTooLong,
+    /// The hardware or library computing the hash failed
+    Backend(&'static str),
+}
+
 pub trait Hasher<const DIGEST_BYTES: usize> {
     fn new_default() -> Self;
     fn update(&mut self, data: &[u8]);
+    /// Fallible version of `update`, for hash functions which may fail
+    /// partway through a message, e.g. ones backed by an accelerator. The
+    /// default calls `update`, which never fails.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), HashError> {
+        self.update(data);
+        Ok(())
+    }
+    /// Finalizes the hash and returns the digest. Calling it again returns
+    /// the same digest, but the hasher must not be updated afterwards.
     fn get_hash(&mut self) -> [u8; DIGEST_BYTES];
+    /// Same as `get_hash`, but writes the digest into out instead of
+    /// returning it. The default copies the digest `get_hash` returns, and
+    /// hashers override it to write their state into out directly.
+    fn get_hash_into(&mut self, out: &mut [u8; DIGEST_BYTES]) {
+        *out = self.get_hash();
+    }
+
+    /// Returns the size of the digest in bytes
+    fn digest_len(&self) -> usize {
+        DIGEST_BYTES
+    }
+
+    /// Restores the initial state, discarding any data hashed so far
+    fn reset(&mut self)
+    where
+        Self: Sized,
+    {
+        *self = Self::new_default();
+    }
+
+    /// Returns the digest, then resets the hasher to its initial state so it
+    /// can be reused for another message
+    fn finalize_reset(&mut self) -> [u8; DIGEST_BYTES]
+    where
+        Self: Sized,
+    {
+        let hash = self.get_hash();
+        self.reset();
+        hash
+    }
+}
+
+/// A `Hasher` which processes its input in blocks of a fixed size, as HMAC
+/// requires
+pub trait BlockHasher<const DIGEST_BYTES: usize>: Hasher<DIGEST_BYTES> {
+    /// Size in bytes of the blocks the hash function processes, which is also
+    /// the key size HMAC expects
+    const BLOCK_BYTES: usize;
+}
+
+/// An object-safe counterpart of `Hasher`, for choosing the hash function at
+/// runtime. Any `Hasher` can be used as a `DynHasher` through `DynHasherWrapper`.
+pub trait DynHasher {
+    fn update(&mut self, data: &[u8]);
+    fn finalize_boxed(&mut self) -> Box<[u8]>;
+    /// Returns the size in bytes of the digest `finalize_boxed` returns
+    fn digest_len(&self) -> usize;
+}
+
+/// Wraps a `Hasher` so it can be used as a `dyn DynHasher`
+pub struct DynHasherWrapper<const DIGEST_BYTES: usize, H: Hasher<DIGEST_BYTES>>(pub H);
+
+impl<const DIGEST_BYTES: usize, H: Hasher<DIGEST_BYTES>> DynHasher
+    for DynHasherWrapper<DIGEST_BYTES, H>
+{
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize_boxed(&mut self) -> Box<[u8]> {
+        Box::new(self.0.get_hash())
+    }
+
+    fn digest_len(&self) -> usize {
+        self.0.digest_len()
+    }
 }
 
-pub struct HMAC<const KEY_BYTES: usize, const DIGEST_BYTES: usize, H: Hasher<DIGEST_BYTES>> {
+/// Returns a new hasher for the hash function with the given name, or `None`
+/// if it is unknown
+pub fn make_hasher(name: &str) -> Option<Box<dyn DynHasher>> {
+    match name {
+        "blake2b" => Some(Box::new(DynHasherWrapper(Blake2b::<64>::new_default()))),
+        "fnv1a32" => Some(Box::new(DynHasherWrapper(Fnv1a32::new_default()))),
+        "fnv1a64" => Some(Box::new(DynHasherWrapper(Fnv1a64::new_default()))),
+        "md5" => Some(Box::new(DynHasherWrapper(MD5::new_default()))),
+        "sha1" => Some(Box::new(DynHasherWrapper(SHA1::new_default()))),
+        "sha224" => Some(Box::new(DynHasherWrapper(SHA224::new_default()))),
+        "sha256" => Some(Box::new(DynHasherWrapper(SHA256::new_default()))),
+        "sha512" => Some(Box::new(DynHasherWrapper(SHA512::new_default()))),
+        _ => None,
+    }
+}
+
+/// Adapts a `Hasher` to `std::io::Write`, so that a stream can be hashed
+/// with `io::copy(&mut reader, &mut hash_writer)`
+pub struct HashWriter<const DIGEST_BYTES: usize, H: Hasher<DIGEST_BYTES>> {
+    hasher: H,
+}
+
+impl<const DIGEST_BYTES: usize, H: Hasher<DIGEST_BYTES>> HashWriter<DIGEST_BYTES, H> {
+    pub fn new(hasher: H) -> Self {
+        HashWriter { hasher }
+    }
+
+    /// Returns the digest of all the data written so far
+    pub fn finish(mut self) -> [u8; DIGEST_BYTES] {
+        self.hasher.get_hash()
+    }
+}
+
+impl<const DIGEST_BYTES: usize, H: Hasher<DIGEST_BYTES>> io::Write for HashWriter<DIGEST_BYTES, H> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.hasher.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Size of the chunks in which `hash_reader` reads its input
+pub const HASH_READER_BUFFER_BYTES: usize = 8192;
+
+/// Hashes everything that can be read from reader, without holding more than
+/// `HASH_READER_BUFFER_BYTES` of it in memory at a time
+pub fn hash_reader<const DIGEST_BYTES: usize, H: Hasher<DIGEST_BYTES>, R: Read>(
+    mut reader: R,
+) -> io::Result<[u8; DIGEST_BYTES]> {
+    let mut hasher = H::new_default();
+    let mut buffer = [0; HASH_READER_BUFFER_BYTES];
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => return Ok(hasher.get_hash()),
+            Ok(n) => hasher.update(&buffer[..n]),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Byte XORed with every byte of the padded key to derive the inner key (RFC 2104)
+const IPAD: u8 = 0x36;
+/// Byte XORed with every byte of the padded key to derive the outer key (RFC 2104)
+const OPAD: u8 = 0x5c;
+
+/// HMAC over the hash function H. `KEY_BYTES` must equal the block size of H,
+/// so it is best written as `HMAC<{ SHA256::BLOCK_BYTES }, 32, SHA256>`; any
+/// other value fails to compile.
+///
+/// A keyed HMAC can be cloned to authenticate several messages under the
+/// same key without running `add_key` again, if H is `Clone`.
+#[derive(Clone)]
+pub struct HMAC<const KEY_BYTES: usize, const DIGEST_BYTES: usize, H: BlockHasher<DIGEST_BYTES>> {
     inner_internal_state: H,
     outer_internal_state: H,
 }
 
-impl<const KEY_BYTES: usize, const DIGEST_BYTES: usize, H: Hasher<DIGEST_BYTES>> HMAC<KEY_BYTES, DIGEST_BYTES, H> {
+impl<const KEY_BYTES: usize, const DIGEST_BYTES: usize, H: BlockHasher<DIGEST_BYTES>>
+    HMAC<KEY_BYTES, DIGEST_BYTES, H>
+{
     pub fn new_default() -> Self {
+        const {
+            assert!(
+                KEY_BYTES == H::BLOCK_BYTES,
+                "`KEY_BYTES` does not match the block size of the hash function."
+            )
+        };
         HMAC {
             inner_internal_state: H::new_default(),
             outer_internal_state: H::new_default(),
@@ -18,21 +189,32 @@ impl<const KEY_BYTES: usize, const DIGEST_BYTES: usize, H: Hasher<DIGEST_BYTES>>
     }
 
     pub fn add_key(&mut self, key: &[u8]) -> Result<(), &'static str> {
+        // Keys longer than the block size are replaced by their hash
+        let hashed_key;
+        let key = if key.len() > KEY_BYTES {
+            let mut hasher = H::new_default();
+            hasher.update(key);
+            hashed_key = hasher.get_hash();
+            &hashed_key[..]
+        } else {
+            key
+        };
         if key.len() <= KEY_BYTES {
-            let mut tmp_key = [0; KEY_BYTES];
-            tmp_key.copy_from_slice(key);
+            // The key is padded with zeros up to the block size
+            let mut padded_key = [0; KEY_BYTES];
+            padded_key[..key.len()].copy_from_slice(key);
 
-            // key ^ IPAD (0x36) should be used as inner key
-            tmp_key.iter_mut().for_each(|b| *b ^= 0x36);
-            self.inner_internal_state.update(&tmp_key);
+            // key ^ IPAD should be used as inner key
+            let inner_key = padded_key.map(|b| b ^ IPAD);
+            self.inner_internal_state.update(&inner_key);
 
-            // key ^ OPAD (0x6a) should be used as outer key
-            tmp_key.iter_mut().for_each(|b| *b ^= 0x6a);
-            self.outer_internal_state.update(&tmp_key);
+            // key ^ OPAD should be used as outer key
+            let outer_key = padded_key.map(|b| b ^ OPAD);
+            self.outer_internal_state.update(&outer_key);
 
             Ok(())
         } else {
-            Err("Key is longer than `KEY_BYTES`.")
+            Err("Digest is longer than `KEY_BYTES`.")
         }
     }
 
@@ -41,16 +223,53 @@ impl<const KEY_BYTES: usize, const DIGEST_BYTES: usize, H: Hasher<DIGEST_BYTES>>
     }
 
     pub fn finalize(&mut self) -> [u8; DIGEST_BYTES] {
-        self.outer_internal_state.update(&self.inner_internal_state.get_hash());
-        self.outer_internal_state.get_hash()
+        let mut out = [0; DIGEST_BYTES];
+        self.finalize_into(&mut out);
+        out
     }
+
+    /// Same as `finalize`, but writes the HMAC into out, e.g. into a buffer
+    /// the caller already owns. Both digests are written with
+    /// `get_hash_into`, so a hasher which overrides it never returns an
+    /// array by value.
+    pub fn finalize_into(&mut self, out: &mut [u8; DIGEST_BYTES]) {
+        // out holds the inner hash until the outer one overwrites it
+        self.inner_internal_state.get_hash_into(out);
+        self.outer_internal_state.update(out);
+        self.outer_internal_state.get_hash_into(out);
+    }
+
+    /// Finalizes the HMAC and checks it against expected. The comparison
+    /// always looks at every byte of the tag, so its running time does not
+    /// depend on where the first mismatch is.
+    pub fn verify(&mut self, expected: &[u8]) -> bool {
+        let tag = self.finalize();
+        let mut difference = u8::from(tag.len() != expected.len());
+        for (i, byte) in tag.iter().enumerate() {
+            difference |= byte ^ expected.get(i).copied().unwrap_or(0);
+        }
+        difference == 0
+    }
+}
+
+/// Computes the HMAC of message under key in one call, e.g.
+/// `hmac::<64, 32, SHA256>(key, message)`
+pub fn hmac<const KEY_BYTES: usize, const DIGEST_BYTES: usize, H: BlockHasher<DIGEST_BYTES>>(
+    key: &[u8],
+    message: &[u8],
+) -> Result<[u8; DIGEST_BYTES], &'static str> {
+    let mut hmac: HMAC<KEY_BYTES, DIGEST_BYTES, H> = HMAC::new_default();
+    hmac.add_key(key)?;
+    hmac.update(message);
+    Ok(hmac.finalize())
 }
 
 #[cfg(test)]
 mod tests {
     use super::super::sha256::tests::get_hash_string;
-    use super::super::SHA256;
-    use super::HMAC;
+    use super::super::{MD5, SHA1, SHA224, SHA256, SHA512};
+    use super::{hash_reader, hmac, make_hasher, BlockHasher, HashError, HashWriter, Hasher, HMAC};
+    use std::io::{self, Cursor, Read, Write};
 
     #[test]
     fn sha256_basic() {
@@ -63,4 +282,427 @@ mod tests {
             "f585fc4536e8e7f378437465b65b6c2eb79036409b18a7d28b6d4c46d3a156f8"
         );
     }
+
+    #[test]
+    fn block_bytes_as_key_bytes() {
+        let mut hmac: HMAC<{ SHA256::BLOCK_BYTES }, 32, SHA256> = HMAC::new_default();
+        hmac.add_key(&[0xde, 0xad, 0xbe, 0xef]).unwrap();
+        hmac.update(b"Hello World");
+        assert_eq!(
+            get_hash_string(&hmac.finalize()),
+            "f585fc4536e8e7f378437465b65b6c2eb79036409b18a7d28b6d4c46d3a156f8"
+        );
+
+        // HMAC<32, 32, SHA256> would not compile
+        let mut hmac: HMAC<{ <SHA512 as BlockHasher<64>>::BLOCK_BYTES }, 64, SHA512> =
+            HMAC::new_default();
+        assert!(hmac.add_key(b"key").is_ok());
+    }
+
+    #[test]
+    fn sha256_rfc4231() {
+        // Test case 1
+        let mut hmac: HMAC<64, 32, SHA256> = HMAC::new_default();
+        hmac.add_key(&[0x0b; 20]).unwrap();
+        hmac.update(b"Hi There");
+        assert_eq!(
+            get_hash_string(&hmac.finalize()),
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"
+        );
+        // Test case 2
+        let mut hmac: HMAC<64, 32, SHA256> = HMAC::new_default();
+        hmac.add_key(b"Jefe").unwrap();
+        hmac.update(b"what do ya want for nothing?");
+        assert_eq!(
+            get_hash_string(&hmac.finalize()),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn sha256_rfc4231_long_key() {
+        // Test case 6
+        let mut hmac: HMAC<64, 32, SHA256> = HMAC::new_default();
+        hmac.add_key(&[0xaa; 131]).unwrap();
+        hmac.update(b"Test Using Larger Than Block-Size Key - Hash Key First");
+        assert_eq!(
+            get_hash_string(&hmac.finalize()),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+        // Test case 7
+        let mut hmac: HMAC<64, 32, SHA256> = HMAC::new_default();
+        hmac.add_key(&[0xaa; 131]).unwrap();
+        hmac.update(
+            b"This is a test using a larger than block-size key and a larger than block-size data. \
+            The key needs to be hashed before being used by the HMAC algorithm.",
+        );
+        assert_eq!(
+            get_hash_string(&hmac.finalize()),
+            "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2"
+        );
+    }
+
+    #[test]
+    fn sha256_short_keys_are_zero_padded() {
+        let cases: [(Vec<u8>, &str); 4] = [
+            (
+                vec![],
+                "fb011e6154a19b9a4c767373c305275a5a69e8b68b0b4c9200c383dced19a416",
+            ),
+            (
+                vec![0x01, 0x02, 0x03, 0x04],
+                "ff7695cdfdf7f97e539e96da5ba0e7e9b0149d963b3c97f79cd998d72c355d15",
+            ),
+            (
+                (0..63).collect(),
+                "3bb4734dab751ec9f48eb9daecd3e5efa13c9a2ee18bf8220546f552c076380d",
+            ),
+            (
+                (0..64).collect(),
+                "4903b1fc9f41bc1abe3ff7119c4e523b91288b11c03dab1e975816150df38144",
+            ),
+        ];
+        for (key, expected) in cases {
+            let mut hmac: HMAC<64, 32, SHA256> = HMAC::new_default();
+            hmac.add_key(&key).unwrap();
+            hmac.update(b"The quick brown fox jumps over the lazy dog");
+            assert_eq!(get_hash_string(&hmac.finalize()), expected);
+        }
+    }
+
+    #[test]
+    fn finalize_reset() {
+        let mut hasher = SHA256::new_default();
+        hasher.update(b"first message");
+        let first = hasher.finalize_reset();
+        hasher.update(b"second message");
+        let second = hasher.finalize_reset();
+
+        let mut fresh = SHA256::new_default();
+        fresh.update(b"first message");
+        assert_eq!(first, fresh.get_hash());
+        let mut fresh = SHA256::new_default();
+        fresh.update(b"second message");
+        assert_eq!(second, fresh.get_hash());
+
+        // after a reset, the hasher is back to the hash of the empty message
+        assert_eq!(hasher.get_hash(), SHA256::new_default().get_hash());
+    }
+
+    #[test]
+    fn hmac_one_shot() {
+        for (key, message) in [
+            (&b"Jefe"[..], &b"what do ya want for nothing?"[..]),
+            (&[0xaa; 131][..], &b"Hello World"[..]),
+            (&[][..], &[][..]),
+        ] {
+            let mut manual: HMAC<64, 32, SHA256> = HMAC::new_default();
+            manual.add_key(key).unwrap();
+            manual.update(message);
+            assert_eq!(
+                hmac::<64, 32, SHA256>(key, message).unwrap(),
+                manual.finalize()
+            );
+        }
+        assert_eq!(
+            get_hash_string(
+                &hmac::<64, 32, SHA256>(b"Jefe", b"what do ya want for nothing?").unwrap()
+            ),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn finalize_into() {
+        // RFC 4231, test case 2
+        let mut mac: HMAC<64, 32, SHA256> = HMAC::new_default();
+        mac.add_key(b"Jefe").unwrap();
+        mac.update(b"what do ya want for nothing?");
+        let mut out = [0xff; 32];
+        mac.finalize_into(&mut out);
+        assert_eq!(
+            get_hash_string(&out),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+
+        let mut mac: HMAC<128, 64, SHA512> = HMAC::new_default();
+        mac.add_key(b"Jefe").unwrap();
+        let mut other = mac.clone();
+        mac.update(b"what do ya want for nothing?");
+        let mut out = [0; 64];
+        mac.finalize_into(&mut out);
+        assert_eq!(
+            get_hash_string(&out),
+            "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554\
+             9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"
+        );
+        other.update(b"what do ya want for nothing?");
+        assert_eq!(other.finalize(), out);
+    }
+
+    #[test]
+    fn get_hash_into() {
+        fn check<const DIGEST_BYTES: usize, H: Hasher<DIGEST_BYTES>>() {
+            for message in [&b""[..], b"abc", &[0x61; 1000]] {
+                let mut hasher = H::new_default();
+                hasher.update(message);
+                let mut out = [0xff; DIGEST_BYTES];
+                hasher.get_hash_into(&mut out);
+                let mut hasher = H::new_default();
+                hasher.update(message);
+                assert_eq!(out, hasher.get_hash());
+            }
+        }
+        check::<32, SHA256>();
+        check::<64, SHA512>();
+        // MD5 uses the default
+        check::<16, MD5>();
+    }
+
+    #[test]
+    fn clone_keyed_hmac() {
+        let mut keyed: HMAC<64, 32, SHA256> = HMAC::new_default();
+        keyed.add_key(b"Jefe").unwrap();
+        let messages: [&[u8]; 3] = [b"what do ya want for nothing?", b"Hello World", b""];
+        let first = keyed.clone();
+        for message in messages {
+            let mut mac = keyed.clone();
+            mac.update(message);
+            assert_eq!(
+                mac.finalize(),
+                hmac::<64, 32, SHA256>(b"Jefe", message).unwrap()
+            );
+        }
+
+        // a clone made partway through a message continues independently
+        let mut mac = first;
+        mac.update(b"what do ya ");
+        let mut other = mac.clone();
+        mac.update(b"want for nothing?");
+        other.update(b"say?");
+        assert_eq!(
+            mac.finalize(),
+            hmac::<64, 32, SHA256>(b"Jefe", b"what do ya want for nothing?").unwrap()
+        );
+        assert_eq!(
+            other.finalize(),
+            hmac::<64, 32, SHA256>(b"Jefe", b"what do ya say?").unwrap()
+        );
+
+        let mut keyed: HMAC<128, 64, SHA512> = HMAC::new_default();
+        keyed.add_key(&[0xaa; 131]).unwrap();
+        let mut mac = keyed.clone();
+        mac.update(b"Hello World");
+        assert_eq!(
+            mac.finalize(),
+            hmac::<128, 64, SHA512>(&[0xaa; 131], b"Hello World").unwrap()
+        );
+    }
+
+    #[test]
+    fn verify() {
+        let tag = hmac::<64, 32, SHA256>(b"Jefe", b"what do ya want for nothing?").unwrap();
+        let new_hmac = |message: &[u8]| {
+            let mut hmac: HMAC<64, 32, SHA256> = HMAC::new_default();
+            hmac.add_key(b"Jefe").unwrap();
+            hmac.update(message);
+            hmac
+        };
+        assert!(new_hmac(b"what do ya want for nothing?").verify(&tag));
+        assert!(!new_hmac(b"what do ya want for nothing!").verify(&tag));
+
+        let mut wrong_tag = tag;
+        wrong_tag[31] ^= 1;
+        assert!(!new_hmac(b"what do ya want for nothing?").verify(&wrong_tag));
+        wrong_tag = tag;
+        wrong_tag[0] ^= 0x80;
+        assert!(!new_hmac(b"what do ya want for nothing?").verify(&wrong_tag));
+
+        // wrong length
+        assert!(!new_hmac(b"what do ya want for nothing?").verify(&tag[..31]));
+        assert!(!new_hmac(b"what do ya want for nothing?").verify(&[tag, tag].concat()));
+        assert!(!new_hmac(b"what do ya want for nothing?").verify(&[]));
+    }
+
+    /// A SHA-256 which refuses to hash more than 10 bytes in total
+    struct LimitedHasher {
+        inner: SHA256,
+        remaining: usize,
+    }
+
+    impl Hasher<32> for LimitedHasher {
+        fn new_default() -> Self {
+            LimitedHasher {
+                inner: SHA256::new_default(),
+                remaining: 10,
+            }
+        }
+
+        fn update(&mut self, data: &[u8]) {
+            self.try_update(data).expect("Limit exceeded");
+        }
+
+        fn try_update(&mut self, data: &[u8]) -> Result<(), HashError> {
+            if data.len() > self.remaining {
+                return Err(HashError::InputTooLong);
+            }
+            self.remaining -= data.len();
+            self.inner.update(data);
+            Ok(())
+        }
+
+        fn get_hash(&mut self) -> [u8; 32] {
+            self.inner.get_hash()
+        }
+    }
+
+    #[test]
+    fn try_update() {
+        let mut hasher = LimitedHasher::new_default();
+        assert_eq!(hasher.try_update(b"Hello"), Ok(()));
+        assert_eq!(hasher.try_update(b" World"), Err(HashError::InputTooLong));
+        // the rejected data is not hashed, so the hasher can go on
+        assert_eq!(hasher.try_update(b" Wor"), Ok(()));
+        assert_eq!(hasher.try_update(b"l"), Ok(()));
+        assert_eq!(hasher.try_update(b"d"), Err(HashError::InputTooLong));
+        assert_eq!(hasher.try_update(b""), Ok(()));
+        let mut reference = SHA256::new_default();
+        reference.update(b"Hello Worl");
+        assert_eq!(hasher.get_hash(), reference.get_hash());
+
+        // the default never fails
+        let mut hasher = SHA256::new_default();
+        assert_eq!(hasher.try_update(b"Hello World"), Ok(()));
+        let mut reference = SHA256::new_default();
+        reference.update(b"Hello World");
+        assert_eq!(hasher.get_hash(), reference.get_hash());
+    }
+
+    #[test]
+    fn digest_len() {
+        assert_eq!(SHA256::new_default().digest_len(), 32);
+        assert_eq!(SHA224::new_default().digest_len(), 28);
+        assert_eq!(SHA512::new_default().digest_len(), 64);
+    }
+
+    #[test]
+    fn dyn_hasher() {
+        let mut hasher = make_hasher("sha256").unwrap();
+        assert_eq!(hasher.digest_len(), 32);
+        hasher.update(b"The quick brown fox ");
+        hasher.update(b"jumps over the lazy dog");
+        let mut reference = SHA256::new_default();
+        reference.update(b"The quick brown fox jumps over the lazy dog");
+        assert_eq!(&hasher.finalize_boxed()[..], &reference.get_hash()[..]);
+
+        let mut hasher = make_hasher("fnv1a32").unwrap();
+        assert_eq!(hasher.digest_len(), 4);
+        hasher.update(b"foobar");
+        assert_eq!(&hasher.finalize_boxed()[..], &[0xbf, 0x9c, 0xf9, 0x68]);
+
+        assert!(make_hasher("unknown").is_none());
+    }
+
+    #[test]
+    fn hash_writer() {
+        let data: Vec<u8> = (0..100_000_u32).map(|i| (i * 31 % 251) as u8).collect();
+        let mut writer = HashWriter::new(SHA256::new_default());
+        io::copy(&mut Cursor::new(data.clone()), &mut writer).unwrap();
+        let mut reference = SHA256::new_default();
+        reference.update(&data);
+        assert_eq!(writer.finish(), reference.get_hash());
+
+        let mut writer = HashWriter::new(SHA256::new_default());
+        let name = "World";
+        write!(writer, "Hello {name}").unwrap();
+        writer.flush().unwrap();
+        let mut reference = SHA256::new_default();
+        reference.update(b"Hello World");
+        assert_eq!(writer.finish(), reference.get_hash());
+    }
+
+    /// A reader which hands out its data a few bytes at a time
+    struct ShortReads<'a>(&'a [u8]);
+
+    impl Read for ShortReads<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.0.len().min(buf.len()).min(7);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn hash_reader_chunks() {
+        // not a multiple of the buffer size, so the last read is partial
+        let data: Vec<u8> = (0..1_000_003_u32).map(|i| (i * 7 % 256) as u8).collect();
+        let mut reference = SHA256::new_default();
+        reference.update(&data);
+        let expected = reference.get_hash();
+
+        let hash = hash_reader::<32, SHA256, _>(Cursor::new(&data)).unwrap();
+        assert_eq!(hash, expected);
+        let hash = hash_reader::<32, SHA256, _>(ShortReads(&data[..1000])).unwrap();
+        let mut reference = SHA256::new_default();
+        reference.update(&data[..1000]);
+        assert_eq!(hash, reference.get_hash());
+
+        let hash = hash_reader::<32, SHA256, _>(io::empty()).unwrap();
+        assert_eq!(hash, SHA256::new_default().get_hash());
+    }
+
+    #[test]
+    fn sha224_hmac() {
+        let mut hmac: HMAC<64, 28, SHA224> = HMAC::new_default();
+        hmac.add_key(b"Jefe").unwrap();
+        hmac.update(b"what do ya want for nothing?");
+        assert_eq!(
+            get_hash_string(&hmac.finalize()),
+            "a30e01098bc6dbbf45690f3a7e9e6d0f8bbea2a39e6148008fd05e44"
+        );
+
+        let mut hasher = make_hasher("sha224").unwrap();
+        hasher.update(b"abc");
+        assert_eq!(
+            get_hash_string(&hasher.finalize_boxed()),
+            "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7"
+        );
+    }
+
+    #[test]
+    fn sha512_hmac() {
+        let mut hmac: HMAC<128, 64, SHA512> = HMAC::new_default();
+        hmac.add_key(b"Jefe").unwrap();
+        hmac.update(b"what do ya want for nothing?");
+        assert_eq!(
+            get_hash_string(&hmac.finalize()),
+            "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554\
+             9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"
+        );
+    }
+
+    #[test]
+    fn md5_hmac() {
+        // RFC 2202, test case 2
+        let mut hmac: HMAC<64, 16, MD5> = HMAC::new_default();
+        hmac.add_key(b"Jefe").unwrap();
+        hmac.update(b"what do ya want for nothing?");
+        assert_eq!(
+            get_hash_string(&hmac.finalize()),
+            "750c783e6ab0b503eaa86e310a5db738"
+        );
+    }
+
+    #[test]
+    fn sha1_hmac() {
+        // RFC 2202, test case 2
+        let mut hmac: HMAC<64, 20, SHA1> = HMAC::new_default();
+        hmac.add_key(b"Jefe").unwrap();
+        hmac.update(b"what do ya want for nothing?");
+        assert_eq!(
+            get_hash_string(&hmac.finalize()),
+            "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79"
+        );
+    }
 }
@@ -0,0 +1,79 @@
+//! Hexadecimal encoding of byte strings, e.g. for printing digests
+
+use std::fmt::Write;
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum HexDecodeError {
+    /// Every byte takes two digits, so the input length must be even
+    OddLength,
+    /// The input contains a character which is not a hexadecimal digit
+    InvalidCharacter(char),
+}
+
+/// Encodes bytes as lowercase hexadecimal digits
+pub fn hex_encode(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(2 * bytes.len());
+    for &byte in bytes {
+        write!(&mut result, "{byte:02x}").unwrap();
+    }
+    result
+}
+
+/// Encodes bytes as uppercase hexadecimal digits
+pub fn hex_encode_upper(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(2 * bytes.len());
+    for &byte in bytes {
+        write!(&mut result, "{byte:02X}").unwrap();
+    }
+    result
+}
+
+/// Decodes a string of hexadecimal digits, in either case, back to bytes
+pub fn hex_decode(s: &str) -> Result<Vec<u8>, HexDecodeError> {
+    let digits = s
+        .chars()
+        .map(|c| {
+            c.to_digit(16)
+                .map(|d| d as u8)
+                .ok_or(HexDecodeError::InvalidCharacter(c))
+        })
+        .collect::<Result<Vec<u8>, HexDecodeError>>()?;
+    if digits.len() % 2 != 0 {
+        return Err(HexDecodeError::OddLength);
+    }
+    Ok(digits
+        .chunks(2)
+        .map(|pair| (pair[0] << 4) | pair[1])
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode() {
+        assert_eq!(hex_encode(&[]), "");
+        assert_eq!(hex_encode(&[0x00, 0x0f, 0xa5, 0xff]), "000fa5ff");
+        assert_eq!(hex_encode_upper(&[0x00, 0x0f, 0xa5, 0xff]), "000FA5FF");
+    }
+
+    #[test]
+    fn round_trip() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(hex_decode(&hex_encode(&bytes)), Ok(bytes.clone()));
+        assert_eq!(hex_decode(&hex_encode_upper(&bytes)), Ok(bytes));
+        assert_eq!(hex_decode("DeadBeef"), Ok(vec![0xde, 0xad, 0xbe, 0xef]));
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(hex_decode("abc"), Err(HexDecodeError::OddLength));
+        assert_eq!(hex_decode("0g"), Err(HexDecodeError::InvalidCharacter('g')));
+        assert_eq!(
+            hex_decode("00 1"),
+            Err(HexDecodeError::InvalidCharacter(' '))
+        );
+        assert_eq!(hex_decode("é0"), Err(HexDecodeError::InvalidCharacter('é')));
+    }
+}
@@ -0,0 +1,113 @@
+//! HKDF, the HMAC-based extract-and-expand key derivation function of
+//! RFC 5869
+//!
+//! Unlike PBKDF2, HKDF is meant for input keying material which already has
+//! enough entropy, such as a Diffie-Hellman shared secret. Extract condenses
+//! it into a pseudorandom key, which expand stretches into as many bytes of
+//! output keying material as needed.
+
+use super::{hmac, BlockHasher};
+
+/// Extracts a pseudorandom key from the input keying material. An empty salt
+/// behaves like a salt of DIGEST_BYTES zeros.
+pub fn hkdf_extract<
+    const KEY_BYTES: usize,
+    const DIGEST_BYTES: usize,
+    H: BlockHasher<DIGEST_BYTES>,
+>(
+    salt: &[u8],
+    ikm: &[u8],
+) -> Result<[u8; DIGEST_BYTES], &'static str> {
+    // HMAC pads keys with zeros, so an empty salt needs no special case
+    hmac::<KEY_BYTES, DIGEST_BYTES, H>(salt, ikm)
+}
+
+/// Fills okm with output keying material derived from the pseudorandom key
+/// and the context specific info. At most 255 * DIGEST_BYTES bytes can be
+/// derived.
+pub fn hkdf_expand<
+    const KEY_BYTES: usize,
+    const DIGEST_BYTES: usize,
+    H: BlockHasher<DIGEST_BYTES>,
+>(
+    prk: &[u8],
+    info: &[u8],
+    okm: &mut [u8],
+) -> Result<(), &'static str> {
+    if okm.len() > 255 * DIGEST_BYTES {
+        return Err("Output keying material too long");
+    }
+    // T(i) = HMAC(PRK, T(i - 1) || info || i), with T(0) empty
+    let mut t: &[u8] = &[];
+    let mut block;
+    for (i, chunk) in okm.chunks_mut(DIGEST_BYTES).enumerate() {
+        let message = [t, info, &[i as u8 + 1]].concat();
+        block = hmac::<KEY_BYTES, DIGEST_BYTES, H>(prk, &message)?;
+        chunk.copy_from_slice(&block[..chunk.len()]);
+        t = &block;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{hex_decode, hex_encode, SHA256};
+    use super::*;
+
+    fn check_rfc5869(ikm: &[u8], salt: &[u8], info: &[u8], prk: &str, okm: &str) {
+        let extracted = hkdf_extract::<64, 32, SHA256>(salt, ikm).unwrap();
+        assert_eq!(hex_encode(&extracted), prk);
+        let mut expanded = vec![0; okm.len() / 2];
+        hkdf_expand::<64, 32, SHA256>(&extracted, info, &mut expanded).unwrap();
+        assert_eq!(hex_encode(&expanded), okm);
+    }
+
+    #[test]
+    fn rfc5869_basic() {
+        check_rfc5869(
+            &[0x0b; 22],
+            &hex_decode("000102030405060708090a0b0c").unwrap(),
+            &hex_decode("f0f1f2f3f4f5f6f7f8f9").unwrap(),
+            "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5",
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf\
+             34007208d5b887185865",
+        );
+    }
+
+    #[test]
+    fn rfc5869_long_inputs() {
+        let ikm: Vec<u8> = (0x00..0x50).collect();
+        let salt: Vec<u8> = (0x60..0xb0).collect();
+        let info: Vec<u8> = (0xb0..=0xff).collect();
+        check_rfc5869(
+            &ikm,
+            &salt,
+            &info,
+            "06a6b88c5853361a06104c9ceb35b45cef760014904671014a193f40c15fc244",
+            "b11e398dc80327a1c8e7f78c596a49344f012eda2d4efad8a050cc4c19afa97c\
+             59045a99cac7827271cb41c65e590e09da3275600c2f09b8367793a9aca3db71\
+             cc30c58179ec3e87c14c01d5c1f3434f1d87",
+        );
+    }
+
+    #[test]
+    fn rfc5869_empty_salt_and_info() {
+        check_rfc5869(
+            &[0x0b; 22],
+            &[],
+            &[],
+            "19ef24a32c717b167f33a91d6f648bdf96596776afdb6377ac434c1c293ccb04",
+            "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d\
+             9d201395faa4b61a96c8",
+        );
+    }
+
+    #[test]
+    fn output_too_long() {
+        let prk = [0; 32];
+        let mut okm = vec![0; 255 * 32];
+        assert!(hkdf_expand::<64, 32, SHA256>(&prk, b"", &mut okm).is_ok());
+        let mut okm = vec![0; 255 * 32 + 1];
+        assert!(hkdf_expand::<64, 32, SHA256>(&prk, b"", &mut okm).is_err());
+    }
+}
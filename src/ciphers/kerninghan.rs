@@ -1,12 +1,12 @@
-pub fn kerninghan(mut n: u32) -> u32 {
-    let mut count = 0;
+use crate::bit_manipulation::{count_set_bits, hamming_weight};
 
-    while n > 0 {
-        n &= n - 1;
-        count += 1;
-    }
+pub fn kerninghan(n: u32) -> u32 {
+    count_set_bits(n)
+}
 
-    count
+/// Returns the number of bit positions in which a and b differ
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+    hamming_weight(a ^ b)
 }
 
 #[cfg(test)]
@@ -19,4 +19,15 @@ mod tests {
         assert_eq!(kerninghan(0b0000_0000_0000_0000_0000_0000_1000_0000), 1);
         assert_eq!(kerninghan(0b1111_1111_1111_1111_1111_1111_1111_1101), 31);
     }
+
+    #[test]
+    fn hamming_distance() {
+        assert_eq!(super::hamming_distance(0, 0), 0);
+        assert_eq!(super::hamming_distance(0xdead_beef, 0xdead_beef), 0);
+        assert_eq!(super::hamming_distance(0, u64::MAX), 64);
+        assert_eq!(super::hamming_distance(0x0f0f, !0x0f0f), 64);
+        assert_eq!(super::hamming_distance(0b1011_1101, 0b1001_1001), 2);
+        assert_eq!(super::hamming_distance(1, 2), 2);
+        assert_eq!(super::hamming_distance(7, 8), 4);
+    }
 }
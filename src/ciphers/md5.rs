@@ -0,0 +1,197 @@
+/*!
+ * MD5 implementation, based on RFC1321
+ *
+ * WARNING: MD5 is cryptographically broken. Collisions can be found in
+ * seconds on commodity hardware, so it must not be used for signatures,
+ * certificates, password hashing or anything else relying on collision
+ * resistance. It is only provided for interoperability with legacy systems
+ * and as a checksum against accidental corruption.
+ */
+
+/// Size of a block in bytes
+const BLOCK_BYTES: usize = 64;
+
+const H0: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+
+// The integer part of 2^32 * abs(sin(i)), for i from 1 to 64
+const K: [u32; 64] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+    0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+    0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+    0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed, 0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+    0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+    0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+    0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+    0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+// Per-round left rotation amounts
+const S: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, //
+    5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20, //
+    4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, //
+    6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+/// MD5 message digest. See the module documentation: this is NOT a secure
+/// hash function.
+#[derive(Clone)]
+pub struct MD5 {
+    /// The current block to be processed, 512 bits long
+    buffer: [u8; BLOCK_BYTES],
+    /// Length (bytes) of the message
+    length: u64,
+    /// The current hash value. Note: this value is invalid unless `get_hash`
+    /// is called
+    h: [u32; 4],
+    finalized: bool,
+}
+
+fn process_block(h: &mut [u32; 4], block: &[u8; BLOCK_BYTES]) {
+    let mut m = [0u32; 16];
+    for (word, bytes) in m.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_le_bytes(bytes.try_into().unwrap());
+    }
+    let [mut a, mut b, mut c, mut d] = *h;
+    for i in 0..64 {
+        // The four auxiliary functions F, G, H and I of RFC1321, and the
+        // order in which each round reads the message words
+        let (f, g) = match i / 16 {
+            0 => ((b & c) | (!b & d), i),
+            1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+            2 => (b ^ c ^ d, (3 * i + 5) % 16),
+            _ => (c ^ (b | !d), (7 * i) % 16),
+        };
+        let rotated = a
+            .wrapping_add(f)
+            .wrapping_add(K[i])
+            .wrapping_add(m[g])
+            .rotate_left(S[i]);
+        a = d;
+        d = c;
+        c = b;
+        b = b.wrapping_add(rotated);
+    }
+    for (h, v) in h.iter_mut().zip([a, b, c, d]) {
+        *h = h.wrapping_add(v);
+    }
+}
+
+impl MD5 {
+    pub fn new_default() -> Self {
+        MD5 {
+            buffer: [0u8; BLOCK_BYTES],
+            length: 0,
+            h: H0,
+            finalized: false,
+        }
+    }
+
+    pub fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            let buf_ind = (self.length % BLOCK_BYTES as u64) as usize;
+            let n = data.len().min(BLOCK_BYTES - buf_ind);
+            self.buffer[buf_ind..buf_ind + n].copy_from_slice(&data[..n]);
+            self.length += n as u64;
+            data = &data[n..];
+            if buf_ind + n == BLOCK_BYTES {
+                process_block(&mut self.h, &self.buffer);
+            }
+        }
+    }
+
+    pub fn get_hash(&mut self) -> [u8; 16] {
+        // Same padding as SHA-256, except that the length is little-endian
+        if !self.finalized {
+            self.finalized = true;
+            let bit_length = self.length.wrapping_shl(3);
+            let buf_ind = (self.length % BLOCK_BYTES as u64) as usize;
+            let num_0 = if buf_ind < BLOCK_BYTES - 8 {
+                BLOCK_BYTES - 8 - buf_ind - 1
+            } else {
+                2 * BLOCK_BYTES - 8 - buf_ind - 1
+            };
+            let mut padding = vec![0u8; num_0 + 9];
+            padding[0] = 0x80;
+            padding[num_0 + 1..].copy_from_slice(&bit_length.to_le_bytes());
+            self.update(&padding);
+        }
+        assert_eq!(self.length % BLOCK_BYTES as u64, 0);
+        let mut result = [0u8; 16];
+        for (bytes, h) in result.chunks_exact_mut(4).zip(self.h) {
+            bytes.copy_from_slice(&h.to_le_bytes());
+        }
+        result
+    }
+}
+
+impl super::BlockHasher<16> for MD5 {
+    const BLOCK_BYTES: usize = BLOCK_BYTES;
+}
+
+impl super::Hasher<16> for MD5 {
+    fn new_default() -> Self {
+        MD5::new_default()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.update(data);
+    }
+
+    fn get_hash(&mut self) -> [u8; 16] {
+        self.get_hash()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::sha256::tests::get_hash_string;
+    use super::*;
+
+    fn md5_string(data: &[u8]) -> String {
+        let mut hasher = MD5::new_default();
+        hasher.update(data);
+        get_hash_string(&hasher.get_hash())
+    }
+
+    // The test suite of RFC1321, appendix A.5
+    #[test]
+    fn rfc1321() {
+        assert_eq!(md5_string(b""), "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(md5_string(b"a"), "0cc175b9c0f1b6a831c399e269772661");
+        assert_eq!(md5_string(b"abc"), "900150983cd24fb0d6963f7d28e17f72");
+        assert_eq!(
+            md5_string(b"message digest"),
+            "f96b697d7cb7938d525a2f31aaf161d0"
+        );
+        assert_eq!(
+            md5_string(b"abcdefghijklmnopqrstuvwxyz"),
+            "c3fcd3d76192e4007dfb496cca67e13b"
+        );
+        assert_eq!(
+            md5_string(b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789"),
+            "d174ab98d277d9f5a5611c2c9f419d9f"
+        );
+        assert_eq!(
+            md5_string(&b"1234567890".repeat(8)),
+            "57edf4a22be3c955ac49da2e2107b67a"
+        );
+    }
+
+    #[test]
+    fn incremental() {
+        let mut hasher = MD5::new_default();
+        for chunk in b"12345678901234567890".repeat(4).chunks(7) {
+            hasher.update(chunk);
+        }
+        assert_eq!(
+            get_hash_string(&hasher.get_hash()),
+            "57edf4a22be3c955ac49da2e2107b67a"
+        );
+        // Test if finalization is not repeated twice
+        assert_eq!(
+            get_hash_string(&hasher.get_hash()),
+            "57edf4a22be3c955ac49da2e2107b67a"
+        );
+    }
+}
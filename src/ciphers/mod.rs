@@ -1,3 +1,4 @@
+mod adler32;
 mod aes;
 mod another_rot13;
 mod baconian_cipher;
@@ -5,41 +6,63 @@ mod base64;
 mod blake2b;
 mod caesar;
 mod chacha;
+mod crc32;
 mod diffie_hellman;
+mod fnv;
 mod hashing_traits;
+mod hex;
+mod hkdf;
 mod kerninghan;
+mod md5;
 mod morse_code;
+mod pbkdf2;
 mod polybius;
 mod rail_fence;
 mod rot13;
 mod salsa;
+mod sha1;
 mod sha256;
 mod sha3;
+mod sha512;
 mod tea;
 mod theoretical_rot13;
 mod transposition;
 mod vigenere;
 mod xor;
+pub use self::adler32::adler32;
 pub use self::aes::{aes_decrypt, aes_encrypt, AesKey};
 pub use self::another_rot13::another_rot13;
 pub use self::baconian_cipher::{baconian_decode, baconian_encode};
-pub use self::base64::{base64_decode, base64_encode};
-pub use self::blake2b::blake2b;
-pub use self::caesar::caesar;
+pub use self::base64::{
+    base64_decode, base64_decode_url_safe, base64_encode, base64_encode_url_safe, DecodeError,
+};
+pub use self::blake2b::{blake2b, Blake2b};
+pub use self::caesar::{caesar, caesar_decrypt, caesar_encrypt};
 pub use self::chacha::chacha20;
+pub use self::crc32::crc32;
 pub use self::diffie_hellman::DiffieHellman;
-pub use self::hashing_traits::Hasher;
-pub use self::hashing_traits::HMAC;
-pub use self::kerninghan::kerninghan;
+pub use self::fnv::{Fnv1a32, Fnv1a64};
+pub use self::hashing_traits::{
+    hash_reader, make_hasher, BlockHasher, DynHasher, DynHasherWrapper, HashError, HashWriter,
+    Hasher, HASH_READER_BUFFER_BYTES,
+};
+pub use self::hashing_traits::{hmac, HMAC};
+pub use self::hex::{hex_decode, hex_encode, hex_encode_upper, HexDecodeError};
+pub use self::hkdf::{hkdf_expand, hkdf_extract};
+pub use self::kerninghan::{hamming_distance, kerninghan};
+pub use self::md5::MD5;
 pub use self::morse_code::{decode, encode};
+pub use self::pbkdf2::pbkdf2;
 pub use self::polybius::{decode_ascii, encode_ascii};
 pub use self::rail_fence::{rail_fence_decrypt, rail_fence_encrypt};
-pub use self::rot13::rot13;
+pub use self::rot13::{rot13, rot_n};
 pub use self::salsa::salsa20;
-pub use self::sha256::SHA256;
+pub use self::sha1::SHA1;
+pub use self::sha256::{sha256, SHA224, SHA256};
 pub use self::sha3::{sha3_224, sha3_256, sha3_384, sha3_512};
+pub use self::sha512::SHA512;
 pub use self::tea::{tea_decrypt, tea_encrypt};
 pub use self::theoretical_rot13::theoretical_rot13;
 pub use self::transposition::transposition;
-pub use self::vigenere::vigenere;
-pub use self::xor::xor;
+pub use self::vigenere::{vigenere, vigenere_decrypt, vigenere_encrypt};
+pub use self::xor::{xor, xor_cipher};
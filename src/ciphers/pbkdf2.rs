@@ -0,0 +1,107 @@
+//! PBKDF2, the password-based key derivation function of RFC 8018
+//!
+//! The derived key is split into blocks of the size of the digest. Block `i`
+//! is `U_1 ^ U_2 ^ ... ^ U_c`, where `U_1 = PRF(password, salt || i)` and
+//! `U_j = PRF(password, U_{j-1})`, the PRF being an HMAC. The iteration count
+//! `c` makes every guess of a brute-force attack proportionally slower.
+
+use super::{hmac, BlockHasher};
+
+/// Fills out with the key derived from password and salt, using
+/// HMAC<KEY_BYTES, DIGEST_BYTES, H> as the pseudorandom function. Fails if
+/// iterations is 0, or if the HMAC parameters are invalid.
+pub fn pbkdf2<const KEY_BYTES: usize, const DIGEST_BYTES: usize, H: BlockHasher<DIGEST_BYTES>>(
+    password: &[u8],
+    salt: &[u8],
+    iterations: u32,
+    out: &mut [u8],
+) -> Result<(), &'static str> {
+    if iterations == 0 {
+        return Err("The iteration count must be at least 1");
+    }
+    for (i, block) in out.chunks_mut(DIGEST_BYTES).enumerate() {
+        let block_index = u32::try_from(i + 1).map_err(|_| "Derived key too long")?;
+        let mut u = hmac::<KEY_BYTES, DIGEST_BYTES, H>(
+            password,
+            &[salt, &block_index.to_be_bytes()].concat(),
+        )?;
+        let mut t = u;
+        for _ in 1..iterations {
+            u = hmac::<KEY_BYTES, DIGEST_BYTES, H>(password, &u)?;
+            for (t, u) in t.iter_mut().zip(u) {
+                *t ^= u;
+            }
+        }
+        // The last block is truncated if needed
+        block.copy_from_slice(&t[..block.len()]);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{hex_encode, SHA1, SHA256};
+    use super::*;
+
+    fn pbkdf2_sha1(password: &[u8], salt: &[u8], iterations: u32, len: usize) -> String {
+        let mut out = vec![0; len];
+        pbkdf2::<64, 20, SHA1>(password, salt, iterations, &mut out).unwrap();
+        hex_encode(&out)
+    }
+
+    fn pbkdf2_sha256(password: &[u8], salt: &[u8], iterations: u32, len: usize) -> String {
+        let mut out = vec![0; len];
+        pbkdf2::<64, 32, SHA256>(password, salt, iterations, &mut out).unwrap();
+        hex_encode(&out)
+    }
+
+    // The test vectors of RFC 6070, which are for HMAC-SHA1
+    #[test]
+    fn rfc6070() {
+        assert_eq!(
+            pbkdf2_sha1(b"password", b"salt", 1, 20),
+            "0c60c80f961f0e71f3a9b524af6012062fe037a6"
+        );
+        assert_eq!(
+            pbkdf2_sha1(b"password", b"salt", 4096, 20),
+            "4b007901b765489abead49d926f721d065a429c1"
+        );
+        assert_eq!(
+            pbkdf2_sha1(
+                b"passwordPASSWORDpassword",
+                b"saltSALTsaltSALTsaltSALTsaltSALTsalt",
+                4096,
+                25
+            ),
+            "3d2eec4fe41c849b80c8d83662c0e44a8b291a964cf2f07038"
+        );
+        assert_eq!(
+            pbkdf2_sha1(b"pass\0word", b"sa\0lt", 4096, 16),
+            "56fa6aa75548099dcc37d7f03425e0c3"
+        );
+    }
+
+    #[test]
+    fn hmac_sha256() {
+        assert_eq!(
+            pbkdf2_sha256(b"password", b"salt", 1, 32),
+            "120fb6cffcf8b32c43e7225256c4f837a86548c92ccc35480805987cb70be17b"
+        );
+        assert_eq!(
+            pbkdf2_sha256(b"password", b"salt", 4096, 32),
+            "c5e478d59288c841aa530db6845c4c8d962893a001ce4e11a4963873aa98134a"
+        );
+        // From RFC 7914, with a key spanning two blocks
+        assert_eq!(
+            pbkdf2_sha256(b"passwd", b"salt", 1, 64),
+            "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc\
+             49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783"
+        );
+    }
+
+    #[test]
+    fn zero_iterations() {
+        let mut out = [0; 32];
+        assert!(pbkdf2::<64, 32, SHA256>(b"password", b"salt", 0, &mut out).is_err());
+    }
+}
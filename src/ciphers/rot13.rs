@@ -1,13 +1,15 @@
+use super::caesar;
+
+/// Rotates every ascii letter by 13 places, preserving case. Since the
+/// alphabet has 26 letters, applying it twice gives back the original text.
 pub fn rot13(text: &str) -> String {
-    let to_enc = text.to_uppercase();
-    to_enc
-        .chars()
-        .map(|c| match c {
-            'A'..='M' => ((c as u8) + 13) as char,
-            'N'..='Z' => ((c as u8) - 13) as char,
-            _ => c,
-        })
-        .collect()
+    rot_n(text, 13)
+}
+
+/// Rotates every ascii letter by n places, preserving case and leaving every
+/// other character unchanged. `rot_n(text, 26 - n)` undoes it.
+pub fn rot_n(text: &str, n: u8) -> String {
+    caesar(text, n)
 }
 
 #[cfg(test)]
@@ -33,4 +35,26 @@ mod test {
     fn test_twice() {
         assert_eq!("ABCD", rot13(&rot13("ABCD")));
     }
+
+    #[test]
+    fn test_preserves_case_and_symbols() {
+        let text = "Why did the chicken cross the road? 42, 3.14 & 😀!";
+        assert_eq!(
+            rot13(text),
+            "Jul qvq gur puvpxra pebff gur ebnq? 42, 3.14 & 😀!"
+        );
+        assert_eq!(rot13(&rot13(text)), text);
+        assert_eq!(rot13("0123456789 !?.,;:-"), "0123456789 !?.,;:-");
+    }
+
+    #[test]
+    fn test_rot_n() {
+        assert_eq!(rot_n("abc XYZ", 1), "bcd YZA");
+        assert_eq!(rot_n("abc XYZ", 0), "abc XYZ");
+        assert_eq!(rot_n("abc XYZ", 26), "abc XYZ");
+        assert_eq!(rot_n("abc XYZ", 13), rot13("abc XYZ"));
+        for n in 0..26 {
+            assert_eq!(rot_n(&rot_n("Hello, World!", n), 26 - n), "Hello, World!");
+        }
+    }
 }
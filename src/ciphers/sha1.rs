@@ -0,0 +1,181 @@
+/*!
+ * SHA-1 implementation, based on RFC3174
+ *
+ * WARNING: SHA-1 is cryptographically broken. Practical collisions have been
+ * demonstrated (SHAttered, 2017), so it must not be used where collision
+ * resistance matters. It is only provided for interoperability, e.g. with git
+ * object ids and legacy protocols.
+ */
+
+/// Size of a block in bytes
+const BLOCK_BYTES: usize = 64;
+
+const H0: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+/// SHA-1 message digest. See the module documentation: this is NOT a secure
+/// hash function.
+#[derive(Clone)]
+pub struct SHA1 {
+    /// The current block to be processed, 512 bits long
+    buffer: [u8; BLOCK_BYTES],
+    /// Length (bytes) of the message
+    length: u64,
+    /// The current hash value. Note: this value is invalid unless `get_hash`
+    /// is called
+    h: [u32; 5],
+    finalized: bool,
+}
+
+fn process_block(h: &mut [u32; 5], block: &[u8; BLOCK_BYTES]) {
+    // Prepare the message schedule:
+    let mut w = [0u32; 80];
+    for (word, bytes) in w.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_be_bytes(bytes.try_into().unwrap());
+    }
+    for i in 16..w.len() {
+        w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+    }
+    let [mut a, mut b, mut c, mut d, mut e] = *h;
+    for (i, &w) in w.iter().enumerate() {
+        let (f, k) = match i / 20 {
+            0 => ((b & c) | (!b & d), 0x5a827999),
+            1 => (b ^ c ^ d, 0x6ed9eba1),
+            2 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+            _ => (b ^ c ^ d, 0xca62c1d6),
+        };
+        let temp = a
+            .rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(w);
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = temp;
+    }
+    for (h, v) in h.iter_mut().zip([a, b, c, d, e]) {
+        *h = h.wrapping_add(v);
+    }
+}
+
+impl SHA1 {
+    pub fn new_default() -> Self {
+        SHA1 {
+            buffer: [0u8; BLOCK_BYTES],
+            length: 0,
+            h: H0,
+            finalized: false,
+        }
+    }
+
+    pub fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            let buf_ind = (self.length % BLOCK_BYTES as u64) as usize;
+            let n = data.len().min(BLOCK_BYTES - buf_ind);
+            self.buffer[buf_ind..buf_ind + n].copy_from_slice(&data[..n]);
+            self.length += n as u64;
+            data = &data[n..];
+            if buf_ind + n == BLOCK_BYTES {
+                process_block(&mut self.h, &self.buffer);
+            }
+        }
+    }
+
+    pub fn get_hash(&mut self) -> [u8; 20] {
+        // Same padding as SHA-256: a `1` bit, then 0s up to (512k + 448) bits,
+        // then the 64 bit length of the message in bits
+        if !self.finalized {
+            self.finalized = true;
+            let bit_length = self.length.wrapping_shl(3);
+            let buf_ind = (self.length % BLOCK_BYTES as u64) as usize;
+            let num_0 = if buf_ind < BLOCK_BYTES - 8 {
+                BLOCK_BYTES - 8 - buf_ind - 1
+            } else {
+                2 * BLOCK_BYTES - 8 - buf_ind - 1
+            };
+            let mut padding = vec![0u8; num_0 + 9];
+            padding[0] = 0x80;
+            padding[num_0 + 1..].copy_from_slice(&bit_length.to_be_bytes());
+            self.update(&padding);
+        }
+        assert_eq!(self.length % BLOCK_BYTES as u64, 0);
+        let mut result = [0u8; 20];
+        for (bytes, h) in result.chunks_exact_mut(4).zip(self.h) {
+            bytes.copy_from_slice(&h.to_be_bytes());
+        }
+        result
+    }
+}
+
+impl super::BlockHasher<20> for SHA1 {
+    const BLOCK_BYTES: usize = BLOCK_BYTES;
+}
+
+impl super::Hasher<20> for SHA1 {
+    fn new_default() -> Self {
+        SHA1::new_default()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.update(data);
+    }
+
+    fn get_hash(&mut self) -> [u8; 20] {
+        self.get_hash()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::sha256::tests::get_hash_string;
+    use super::*;
+
+    // To test the hashes, you can use the following command on linux:
+    // echo -n 'STRING' | sha1sum
+
+    #[test]
+    fn empty() {
+        let mut res = SHA1::new_default();
+        assert_eq!(
+            &get_hash_string(&res.get_hash()),
+            "da39a3ee5e6b4b0d3255bfef95601890afd80709"
+        );
+    }
+
+    #[test]
+    fn abc() {
+        let mut res = SHA1::new_default();
+        res.update(b"abc");
+        assert_eq!(
+            &get_hash_string(&res.get_hash()),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+        // Test if finalization is not repeated twice
+        assert_eq!(
+            &get_hash_string(&res.get_hash()),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+    }
+
+    #[test]
+    fn multi_block() {
+        // 56 bytes, so the padding needs a second block
+        let mut res = SHA1::new_default();
+        res.update(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq");
+        assert_eq!(
+            &get_hash_string(&res.get_hash()),
+            "84983e441c3bd26ebaae4aa1f95129e5e54670f1"
+        );
+
+        let mut res = SHA1::new_default();
+        for _ in 0..1000 {
+            res.update(b"a");
+        }
+        assert_eq!(
+            &get_hash_string(&res.get_hash()),
+            "291e9a6c66994949b57ba5e650361e98fc36b1ba"
+        );
+    }
+}
@@ -1,5 +1,5 @@
 /*!
- * SHA-2 256 bit implementation
+ * SHA-2 256 bit implementation, and the SHA-224 variant built on it
  * This implementation is based on RFC6234
  * Keep in mind that the amount of data (in bits) processed should always be an
  * integer multiple of 8
@@ -10,6 +10,11 @@ pub const H0: [u32; 8] = [
     0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
 ];
 
+/// Initial hash value of SHA-224, see section 6.1 of RFC6234
+pub const H0_224: [u32; 8] = [
+    0xc1059ed8, 0x367cd507, 0x3070dd17, 0xf70e5939, 0xffc00b31, 0x68581511, 0x64f98fa7, 0xbefa4fa4,
+];
+
 pub const K: [u32; 64] = [
     0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
     0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
@@ -52,11 +57,15 @@ fn ssig1(x: u32) -> u32 {
     x.rotate_right(17) ^ x.rotate_right(19) ^ (x >> 10)
 }
 
+#[derive(Clone)]
 pub struct SHA256 {
     /// The current block to be processed, 512 bits long
     buffer: [u32; 16],
     /// Length (bits) of the message, should always be a multiple of 8
     length: u64,
+    /// Length (bits) of the message before the padding was appended, only
+    /// valid once `finalized` is set
+    message_length: u64,
     /// The current hash value. Note: this value is invalid unless `finalize`
     /// is called
     pub h: [u32; 8],
@@ -99,16 +108,45 @@ fn process_block(h: &mut [u32; 8], w: &mut [u32; 64], round: &mut [u32; 8], buf:
 }
 
 impl SHA256 {
+    /// Size in bytes of the blocks SHA-256 processes
+    pub const BLOCK_BYTES: usize = 64;
+
     pub fn new_default() -> Self {
+        Self::with_initial_hash(H0)
+    }
+
+    fn with_initial_hash(h: [u32; 8]) -> Self {
         SHA256 {
             buffer: [0u32; 16],
             length: 0,
-            h: H0,
+            message_length: 0,
+            h,
             w: [0u32; 64],
             round: [0u32; 8],
             finalized: false,
         }
     }
+
+    /// Restores the initial state, so the hasher can be reused without
+    /// constructing a new one
+    pub fn reset(&mut self) {
+        self.buffer = [0u32; 16];
+        self.length = 0;
+        self.message_length = 0;
+        self.h = H0;
+        self.finalized = false;
+    }
+
+    /// Returns the number of message bytes hashed so far. The padding added
+    /// by `get_hash` is not counted.
+    pub fn bytes_processed(&self) -> u64 {
+        if self.finalized {
+            self.message_length >> 3
+        } else {
+            self.length >> 3
+        }
+    }
+
     /// Note: buffer should be empty before calling this!
     pub fn process_block(&mut self, buf: &[u32; 16]) {
         process_block(&mut self.h, &mut self.w, &mut self.round, buf);
@@ -156,11 +194,19 @@ impl SHA256 {
     }
 
     pub fn get_hash(&mut self) -> [u8; 32] {
+        let mut result = [0u8; 32];
+        self.get_hash_into(&mut result);
+        result
+    }
+
+    /// Same as `get_hash`, but writes the digest into out
+    pub fn get_hash_into(&mut self, out: &mut [u8; 32]) {
         // we should first add a `1` bit to the end of the buffer, then we will
         // add enough 0s so that the length becomes (512k + 448). After that we
         // will append the binary representation of length to the data
         if !self.finalized {
             self.finalized = true;
+            self.message_length = self.length;
             let clen = (self.length + 8) & 511;
             let num_0 = match clen.cmp(&448) {
                 std::cmp::Ordering::Greater => (448 + 512 - clen) >> 3,
@@ -180,17 +226,19 @@ impl SHA256 {
             self.update(&padding);
         }
         assert_eq!(self.length & 511, 0);
-        let mut result = [0u8; 32];
         for i in (0..32).step_by(4) {
-            result[i] = (self.h[i >> 2] >> 24) as u8;
-            result[i + 1] = (self.h[i >> 2] >> 16) as u8;
-            result[i + 2] = (self.h[i >> 2] >> 8) as u8;
-            result[i + 3] = self.h[i >> 2] as u8;
+            out[i] = (self.h[i >> 2] >> 24) as u8;
+            out[i + 1] = (self.h[i >> 2] >> 16) as u8;
+            out[i + 2] = (self.h[i >> 2] >> 8) as u8;
+            out[i + 3] = self.h[i >> 2] as u8;
         }
-        result
     }
 }
 
+impl super::BlockHasher<32> for SHA256 {
+    const BLOCK_BYTES: usize = SHA256::BLOCK_BYTES;
+}
+
 impl super::Hasher<32> for SHA256 {
     fn new_default() -> Self {
         SHA256::new_default()
@@ -203,22 +251,71 @@ impl super::Hasher<32> for SHA256 {
     fn get_hash(&mut self) -> [u8; 32] {
         self.get_hash()
     }
+
+    fn get_hash_into(&mut self, out: &mut [u8; 32]) {
+        self.get_hash_into(out);
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+}
+
+/// Convenience function hashing a single byte slice with SHA-256
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = SHA256::new_default();
+    hasher.update(data);
+    hasher.get_hash()
+}
+
+/// SHA-224 uses the same compression function as SHA-256, but starts from a
+/// different initial hash value and truncates the digest to 28 bytes
+#[derive(Clone)]
+pub struct SHA224(SHA256);
+
+impl SHA224 {
+    pub fn new_default() -> Self {
+        SHA224(SHA256::with_initial_hash(H0_224))
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    pub fn get_hash(&mut self) -> [u8; 28] {
+        let mut result = [0u8; 28];
+        result.copy_from_slice(&self.0.get_hash()[..28]);
+        result
+    }
+}
+
+impl super::BlockHasher<28> for SHA224 {
+    const BLOCK_BYTES: usize = SHA256::BLOCK_BYTES;
+}
+
+impl super::Hasher<28> for SHA224 {
+    fn new_default() -> Self {
+        SHA224::new_default()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.update(data);
+    }
+
+    fn get_hash(&mut self) -> [u8; 28] {
+        self.get_hash()
+    }
 }
 
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use crate::ciphers::hex_encode;
     use crate::math::LinearSieve;
-    use std::fmt::Write;
 
     // Let's keep this utility function
-    pub fn get_hash_string(hash: &[u8; 32]) -> String {
-        let mut result = String::new();
-        result.reserve(64);
-        for &ch in hash {
-            write!(&mut result, "{ch:02x}").unwrap();
-        }
-        result
+    pub fn get_hash_string(hash: &[u8]) -> String {
+        hex_encode(hash)
     }
 
     #[test]
@@ -337,4 +434,73 @@ pub mod tests {
             "ca978112ca1bbdcafac231b39a23dc4da786eff8147c4e72b9807785afee48bb"
         );
     }
+
+    #[test]
+    fn sha224() {
+        let mut res = SHA224::new_default();
+        assert_eq!(
+            &get_hash_string(&res.get_hash()),
+            "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f"
+        );
+        let mut res = SHA224::new_default();
+        res.update(b"abc");
+        assert_eq!(
+            &get_hash_string(&res.get_hash()),
+            "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7"
+        );
+        let mut res = SHA224::new_default();
+        res.update(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq");
+        assert_eq!(
+            &get_hash_string(&res.get_hash()),
+            "75388b16512776cc5dba5da1fd890150b0c6455cb4f58b1952522525"
+        );
+    }
+
+    #[test]
+    fn reset() {
+        let mut res = SHA256::new_default();
+        res.update(b"The quick brown fox jumps over the lazy dog");
+        res.get_hash();
+        res.reset();
+        res.update(b"a");
+        let mut fresh = SHA256::new_default();
+        fresh.update(b"a");
+        assert_eq!(res.get_hash(), fresh.get_hash());
+
+        // Resetting without finalizing discards the pending data too
+        res.reset();
+        res.update(b"The quick brown fox jumps over the lazy dog");
+        res.reset();
+        assert_eq!(res.get_hash(), SHA256::new_default().get_hash());
+    }
+
+    #[test]
+    fn bytes_processed() {
+        let mut res = SHA256::new_default();
+        assert_eq!(res.bytes_processed(), 0);
+        let mut expected = 0;
+        for size in [1, 3, 64, 100, 0, 7] {
+            res.update(&vec![b'a'; size]);
+            expected += size as u64;
+            assert_eq!(res.bytes_processed(), expected);
+        }
+        res.get_hash();
+        assert_eq!(res.bytes_processed(), expected);
+        res.get_hash();
+        assert_eq!(res.bytes_processed(), expected);
+        res.reset();
+        assert_eq!(res.bytes_processed(), 0);
+    }
+
+    #[test]
+    fn one_shot() {
+        assert_eq!(
+            &get_hash_string(&sha256(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            &get_hash_string(&sha256(b"")),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
 }
@@ -0,0 +1,323 @@
+/*!
+ * SHA-2 512 bit implementation
+ * This implementation is based on RFC6234
+ * Keep in mind that the amount of data (in bits) processed should always be an
+ * integer multiple of 8
+ */
+
+// The first 64 bits of the fractional parts of the square roots of the first
+// 8 primes
+pub const H0: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+// The first 64 bits of the fractional parts of the cube roots of the first
+// 80 primes
+pub const K: [u64; 80] = [
+    0x428a2f98d728ae22,
+    0x7137449123ef65cd,
+    0xb5c0fbcfec4d3b2f,
+    0xe9b5dba58189dbbc,
+    0x3956c25bf348b538,
+    0x59f111f1b605d019,
+    0x923f82a4af194f9b,
+    0xab1c5ed5da6d8118,
+    0xd807aa98a3030242,
+    0x12835b0145706fbe,
+    0x243185be4ee4b28c,
+    0x550c7dc3d5ffb4e2,
+    0x72be5d74f27b896f,
+    0x80deb1fe3b1696b1,
+    0x9bdc06a725c71235,
+    0xc19bf174cf692694,
+    0xe49b69c19ef14ad2,
+    0xefbe4786384f25e3,
+    0x0fc19dc68b8cd5b5,
+    0x240ca1cc77ac9c65,
+    0x2de92c6f592b0275,
+    0x4a7484aa6ea6e483,
+    0x5cb0a9dcbd41fbd4,
+    0x76f988da831153b5,
+    0x983e5152ee66dfab,
+    0xa831c66d2db43210,
+    0xb00327c898fb213f,
+    0xbf597fc7beef0ee4,
+    0xc6e00bf33da88fc2,
+    0xd5a79147930aa725,
+    0x06ca6351e003826f,
+    0x142929670a0e6e70,
+    0x27b70a8546d22ffc,
+    0x2e1b21385c26c926,
+    0x4d2c6dfc5ac42aed,
+    0x53380d139d95b3df,
+    0x650a73548baf63de,
+    0x766a0abb3c77b2a8,
+    0x81c2c92e47edaee6,
+    0x92722c851482353b,
+    0xa2bfe8a14cf10364,
+    0xa81a664bbc423001,
+    0xc24b8b70d0f89791,
+    0xc76c51a30654be30,
+    0xd192e819d6ef5218,
+    0xd69906245565a910,
+    0xf40e35855771202a,
+    0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8,
+    0x1e376c085141ab53,
+    0x2748774cdf8eeb99,
+    0x34b0bcb5e19b48a8,
+    0x391c0cb3c5c95a63,
+    0x4ed8aa4ae3418acb,
+    0x5b9cca4f7763e373,
+    0x682e6ff3d6b2b8a3,
+    0x748f82ee5defb2fc,
+    0x78a5636f43172f60,
+    0x84c87814a1f0ab72,
+    0x8cc702081a6439ec,
+    0x90befffa23631e28,
+    0xa4506cebde82bde9,
+    0xbef9a3f7b2c67915,
+    0xc67178f2e372532b,
+    0xca273eceea26619c,
+    0xd186b8c721c0c207,
+    0xeada7dd6cde0eb1e,
+    0xf57d4f7fee6ed178,
+    0x06f067aa72176fba,
+    0x0a637dc5a2c898a6,
+    0x113f9804bef90dae,
+    0x1b710b35131c471b,
+    0x28db77f523047d84,
+    0x32caab7b40c72493,
+    0x3c9ebe0a15c9bebc,
+    0x431d67c49c100d4c,
+    0x4cc5d4becb3e42b6,
+    0x597f299cfc657e2a,
+    0x5fcb6fab3ad6faec,
+    0x6c44198c4a475817,
+];
+
+// The following functions are implemented according to page 11 of RFC6234
+#[inline]
+fn ch(x: u64, y: u64, z: u64) -> u64 {
+    (x & y) ^ ((!x) & z)
+}
+
+#[inline]
+fn maj(x: u64, y: u64, z: u64) -> u64 {
+    (x & y) ^ (x & z) ^ (y & z)
+}
+
+#[inline]
+fn bsig0(x: u64) -> u64 {
+    x.rotate_right(28) ^ x.rotate_right(34) ^ x.rotate_right(39)
+}
+
+#[inline]
+fn bsig1(x: u64) -> u64 {
+    x.rotate_right(14) ^ x.rotate_right(18) ^ x.rotate_right(41)
+}
+
+#[inline]
+fn ssig0(x: u64) -> u64 {
+    x.rotate_right(1) ^ x.rotate_right(8) ^ (x >> 7)
+}
+
+#[inline]
+fn ssig1(x: u64) -> u64 {
+    x.rotate_right(19) ^ x.rotate_right(61) ^ (x >> 6)
+}
+
+/// Size of a block in bytes
+const BLOCK_BYTES: usize = 128;
+
+#[derive(Clone)]
+pub struct SHA512 {
+    /// The current block to be processed, 1024 bits long
+    buffer: [u8; BLOCK_BYTES],
+    /// Length (bytes) of the message
+    length: u128,
+    /// The current hash value. Note: this value is invalid unless `get_hash`
+    /// is called
+    h: [u64; 8],
+    finalized: bool,
+}
+
+fn process_block(h: &mut [u64; 8], block: &[u8; BLOCK_BYTES]) {
+    // Prepare the message schedule:
+    let mut w = [0u64; 80];
+    for (word, bytes) in w.iter_mut().zip(block.chunks_exact(8)) {
+        *word = u64::from_be_bytes(bytes.try_into().unwrap());
+    }
+    for i in 16..w.len() {
+        w[i] = ssig1(w[i - 2])
+            .wrapping_add(w[i - 7])
+            .wrapping_add(ssig0(w[i - 15]))
+            .wrapping_add(w[i - 16]);
+    }
+    let mut round = *h;
+    for (&k, &w) in K.iter().zip(w.iter()) {
+        let t1 = round[7]
+            .wrapping_add(bsig1(round[4]))
+            .wrapping_add(ch(round[4], round[5], round[6]))
+            .wrapping_add(k)
+            .wrapping_add(w);
+        let t2 = bsig0(round[0]).wrapping_add(maj(round[0], round[1], round[2]));
+        round[7] = round[6];
+        round[6] = round[5];
+        round[5] = round[4];
+        round[4] = round[3].wrapping_add(t1);
+        round[3] = round[2];
+        round[2] = round[1];
+        round[1] = round[0];
+        round[0] = t1.wrapping_add(t2);
+    }
+    for (h, round) in h.iter_mut().zip(round) {
+        *h = h.wrapping_add(round);
+    }
+}
+
+impl SHA512 {
+    pub fn new_default() -> Self {
+        SHA512 {
+            buffer: [0u8; BLOCK_BYTES],
+            length: 0,
+            h: H0,
+            finalized: false,
+        }
+    }
+
+    pub fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            let buf_ind = (self.length % BLOCK_BYTES as u128) as usize;
+            let n = data.len().min(BLOCK_BYTES - buf_ind);
+            self.buffer[buf_ind..buf_ind + n].copy_from_slice(&data[..n]);
+            self.length += n as u128;
+            data = &data[n..];
+            if buf_ind + n == BLOCK_BYTES {
+                process_block(&mut self.h, &self.buffer);
+            }
+        }
+    }
+
+    pub fn get_hash(&mut self) -> [u8; 64] {
+        let mut result = [0u8; 64];
+        self.get_hash_into(&mut result);
+        result
+    }
+
+    /// Same as `get_hash`, but writes the digest into out
+    pub fn get_hash_into(&mut self, out: &mut [u8; 64]) {
+        // we should first add a `1` bit to the end of the message, then we
+        // will add enough 0s so that the length becomes (1024k + 896). After
+        // that we will append the 128 bit representation of the length (in
+        // bits) of the message
+        if !self.finalized {
+            self.finalized = true;
+            let bit_length = self.length << 3;
+            let buf_ind = (self.length % BLOCK_BYTES as u128) as usize;
+            let num_0 = if buf_ind < BLOCK_BYTES - 16 {
+                BLOCK_BYTES - 16 - buf_ind - 1
+            } else {
+                2 * BLOCK_BYTES - 16 - buf_ind - 1
+            };
+            let mut padding = vec![0u8; num_0 + 17];
+            padding[0] = 0x80;
+            padding[num_0 + 1..].copy_from_slice(&bit_length.to_be_bytes());
+            self.update(&padding);
+        }
+        assert_eq!(self.length % BLOCK_BYTES as u128, 0);
+        for (bytes, h) in out.chunks_exact_mut(8).zip(self.h) {
+            bytes.copy_from_slice(&h.to_be_bytes());
+        }
+    }
+}
+
+impl super::BlockHasher<64> for SHA512 {
+    const BLOCK_BYTES: usize = BLOCK_BYTES;
+}
+
+impl super::Hasher<64> for SHA512 {
+    fn new_default() -> Self {
+        SHA512::new_default()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.update(data);
+    }
+
+    fn get_hash(&mut self) -> [u8; 64] {
+        self.get_hash()
+    }
+
+    fn get_hash_into(&mut self, out: &mut [u8; 64]) {
+        self.get_hash_into(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::sha256::tests::get_hash_string;
+    use super::*;
+
+    // To test the hashes, you can use the following command on linux:
+    // echo -n 'STRING' | sha512sum
+
+    #[test]
+    fn empty() {
+        let mut res = SHA512::new_default();
+        assert_eq!(
+            &get_hash_string(&res.get_hash()),
+            "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce\
+             47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e"
+        );
+    }
+
+    #[test]
+    fn abc() {
+        let mut res = SHA512::new_default();
+        res.update(b"abc");
+        assert_eq!(
+            &get_hash_string(&res.get_hash()),
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+             2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+        );
+        // Test if finalization is not repeated twice
+        assert_eq!(
+            &get_hash_string(&res.get_hash()),
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+             2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+        );
+    }
+
+    #[test]
+    fn multi_block() {
+        // 112 bytes, so the padding needs a second block
+        let mut res = SHA512::new_default();
+        res.update(
+            b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmno\
+              ijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu",
+        );
+        assert_eq!(
+            &get_hash_string(&res.get_hash()),
+            "8e959b75dae313da8cf4f72814fc143f8f7779c6eb9f7fa17299aeadb6889018\
+             501d289e4900f7e4331b99dec4b5433ac7d329eeb6dd26545e96e55b874be909"
+        );
+
+        let mut res = SHA512::new_default();
+        for _ in 0..1000 {
+            res.update(b"a");
+        }
+        assert_eq!(
+            &get_hash_string(&res.get_hash()),
+            "67ba5535a46e3f86dbfbed8cbbaf0125c76ed549ff8b0b9e03e0c88cf90fa634\
+             fa7b12b47d77b694de488ace8d9a65967dc96df599727d3292a8d9d447709c97"
+        );
+    }
+}
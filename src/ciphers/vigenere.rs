@@ -8,23 +8,51 @@
 
 /// Vigenère cipher to rotate plain_text text by key and return an owned String.
 pub fn vigenere(plain_text: &str, key: &str) -> String {
-    // Remove all unicode and non-ascii characters from key
-    let key: String = key.chars().filter(|&c| c.is_ascii_alphabetic()).collect();
-    let key = key.to_ascii_lowercase();
-
-    let key_len = key.len();
-    if key_len == 0 {
+    let key = alphabetic_key(key);
+    if key.is_empty() {
         return String::from(plain_text);
     }
+    rotate(plain_text, &key, false)
+}
 
+/// Encrypts plain_text with the letters of key, skipping non-letters of the
+/// text without advancing the key. Returns an error if key has no letters.
+pub fn vigenere_encrypt(plain_text: &str, key: &str) -> Result<String, &'static str> {
+    let key = alphabetic_key(key);
+    if key.is_empty() {
+        return Err("Key must contain at least one ascii letter");
+    }
+    Ok(rotate(plain_text, &key, false))
+}
+
+/// Reverses `vigenere_encrypt` with the same key.
+pub fn vigenere_decrypt(cipher_text: &str, key: &str) -> Result<String, &'static str> {
+    let key = alphabetic_key(key);
+    if key.is_empty() {
+        return Err("Key must contain at least one ascii letter");
+    }
+    Ok(rotate(cipher_text, &key, true))
+}
+
+// Remove all unicode and non-ascii characters from key
+fn alphabetic_key(key: &str) -> Vec<u8> {
+    key.chars()
+        .filter(|&c| c.is_ascii_alphabetic())
+        .map(|c| c.to_ascii_lowercase() as u8)
+        .collect()
+}
+
+fn rotate(text: &str, key: &[u8], decrypt: bool) -> String {
     let mut index = 0;
 
-    plain_text
-        .chars()
+    text.chars()
         .map(|c| {
             if c.is_ascii_alphabetic() {
                 let first = if c.is_ascii_lowercase() { b'a' } else { b'A' };
-                let shift = key.as_bytes()[index % key_len] - b'a';
+                let mut shift = key[index % key.len()] - b'a';
+                if decrypt {
+                    shift = (26 - shift) % 26;
+                }
                 index += 1;
                 // modulo the distance to keep character range
                 (first + (c as u8 + shift - first) % 26) as char
@@ -83,4 +111,37 @@ mod tests {
     fn vigenere_empty_key() {
         assert_eq!(vigenere("Lorem ipsum", ""), "Lorem ipsum");
     }
+
+    #[test]
+    fn vigenere_classic_example() {
+        assert_eq!(
+            vigenere_encrypt("ATTACKATDAWN", "LEMON"),
+            Ok(String::from("LXFOPVEFRNHR"))
+        );
+        assert_eq!(
+            vigenere_decrypt("LXFOPVEFRNHR", "LEMON"),
+            Ok(String::from("ATTACKATDAWN"))
+        );
+        // Non-letters do not consume key characters
+        assert_eq!(
+            vigenere_encrypt("attack at dawn!", "lemon"),
+            Ok(String::from("lxfopv ef rnhr!"))
+        );
+    }
+
+    #[test]
+    fn vigenere_round_trip() {
+        let plain = "1 Lorem ⏳ ipsum dolor sit amet, Zz Ѡ";
+        for key in ["a", "z", "unicode", "😉 key!", "LeMoN"] {
+            let encrypted = vigenere_encrypt(plain, key).unwrap();
+            assert_eq!(encrypted, vigenere(plain, key));
+            assert_eq!(vigenere_decrypt(&encrypted, key).unwrap(), plain);
+        }
+    }
+
+    #[test]
+    fn vigenere_rejects_empty_key() {
+        assert!(vigenere_encrypt("Lorem ipsum", "").is_err());
+        assert!(vigenere_decrypt("Lorem ipsum", "123 !").is_err());
+    }
 }
@@ -6,6 +6,19 @@ pub fn xor(text: &str, key: u8) -> Vec<u8> {
     xor_bytes(text.as_bytes(), key)
 }
 
+/// XORs every byte of data with the key, repeated as many times as needed.
+/// Applying it twice with the same key gives back data.
+pub fn xor_cipher(data: &[u8], key: &[u8]) -> Result<Vec<u8>, &'static str> {
+    if key.is_empty() {
+        return Err("Key cannot be empty");
+    }
+    Ok(data
+        .iter()
+        .zip(key.iter().cycle())
+        .map(|(c, k)| c ^ k)
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -47,4 +60,34 @@ mod tests {
         let ciphered_text = xor(test_string, key);
         assert_eq!(test_string.as_bytes(), xor_bytes(&ciphered_text, key));
     }
+
+    #[test]
+    fn test_repeating_key_round_trip() {
+        let data: Vec<u8> = (0..=255).chain(0..100).collect();
+        for key in [&b"k"[..], b"key", &[0, 0xff, 0x80, 0x01, 0x7f]] {
+            let ciphered = xor_cipher(&data, key).unwrap();
+            assert_eq!(ciphered.len(), data.len());
+            assert_eq!(xor_cipher(&ciphered, key).unwrap(), data);
+        }
+        assert_eq!(xor_cipher(&[], b"key"), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_repeating_key_known_answer() {
+        // A single-byte key behaves like xor_bytes
+        assert_eq!(xor_cipher(b"abc", &[0x20]), Ok(b"ABC".to_vec()));
+        assert_eq!(
+            xor_cipher(&[0x00, 0x0f, 0xf0, 0xff], &[0xaa]),
+            Ok(vec![0xaa, 0xa5, 0x5a, 0x55])
+        );
+        assert_eq!(
+            xor_cipher(&[0x00, 0x00, 0x00, 0x00, 0x00], &[0x01, 0x02]),
+            Ok(vec![0x01, 0x02, 0x01, 0x02, 0x01])
+        );
+    }
+
+    #[test]
+    fn test_empty_key() {
+        assert!(xor_cipher(b"data", &[]).is_err());
+    }
 }
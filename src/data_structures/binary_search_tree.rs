@@ -1,15 +1,28 @@
+use crate::math::Rng;
 use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::fmt::Debug;
 use std::ops::Deref;
+use std::sync::Arc;
 
 /// This struct implements as Binary Search Tree (BST), which is a
-/// simple data structure for storing sorted data
+/// simple data structure for storing sorted data.
+///
+/// The subtrees are shared through `Arc`, so cloning a tree is O(1): the
+/// clone copies the root value and shares all other nodes with the original.
+/// Mutating a tree copies the shared nodes on the path it changes before
+/// writing to them (copy-on-write), which is why the methods that mutate a
+/// tree require `T: Clone`.
+#[derive(Clone)]
 pub struct BinarySearchTree<T>
 where
     T: Ord,
 {
     value: Option<T>,
-    left: Option<Box<BinarySearchTree<T>>>,
-    right: Option<Box<BinarySearchTree<T>>>,
+    left: Option<Arc<BinarySearchTree<T>>>,
+    right: Option<Arc<BinarySearchTree<T>>>,
+    /// Number of values stored in this subtree
+    size: usize,
 }
 
 impl<T> Default for BinarySearchTree<T>
@@ -21,6 +34,50 @@ where
     }
 }
 
+impl<T> FromIterator<T> for BinarySearchTree<T>
+where
+    T: Ord + Clone,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = BinarySearchTree::new();
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<T> Extend<T> for BinarySearchTree<T>
+where
+    T: Ord + Clone,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+/// Two trees are equal if they hold the same values, whatever their shape
+impl<T> PartialEq for BinarySearchTree<T>
+where
+    T: Ord,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T> Eq for BinarySearchTree<T> where T: Ord {}
+
+/// A tree is formatted as the set of its values in order
+impl<T> Debug for BinarySearchTree<T>
+where
+    T: Ord + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
 impl<T> BinarySearchTree<T>
 where
     T: Ord,
@@ -31,7 +88,159 @@ where
             value: None,
             left: None,
             right: None,
+            size: 0,
+        }
+    }
+
+    /// Build a balanced BST out of sorted values in O(n).
+    ///
+    /// The values must be sorted in strictly increasing order, i.e. without
+    /// duplicates. The resulting tree has the minimum possible height.
+    pub fn from_sorted(values: Vec<T>) -> BinarySearchTree<T> {
+        debug_assert!(
+            values.windows(2).all(|pair| pair[0] < pair[1]),
+            "from_sorted requires strictly increasing values"
+        );
+        let len = values.len();
+        Self::build_balanced(&mut values.into_iter(), len)
+    }
+
+    /// Builds a balanced subtree out of the next `len` values of `values`,
+    /// consuming them in order: the left half, then the root, then the
+    /// right half
+    fn build_balanced(values: &mut impl Iterator<Item = T>, len: usize) -> BinarySearchTree<T> {
+        if len == 0 {
+            return BinarySearchTree::new();
+        }
+        let left_len = len / 2;
+        let left = Self::build_balanced(values, left_len);
+        let value = values.next();
+        let right = Self::build_balanced(values, len - left_len - 1);
+        BinarySearchTree {
+            value,
+            left: (left_len > 0).then(|| Arc::new(left)),
+            right: (len - left_len > 1).then(|| Arc::new(right)),
+            size: len,
+        }
+    }
+
+    /// Returns the number of values in this tree
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Returns true if this tree contains no values
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Removes all values from this tree. The nodes are dropped, as each
+    /// of them is a separate allocation that can't be reused, unless they
+    /// are still shared with a clone of this tree.
+    pub fn clear(&mut self) {
+        *self = BinarySearchTree::new();
+    }
+
+    /// Returns the number of levels in this tree, which is 0 for an empty
+    /// tree and 1 for a tree holding a single value
+    pub fn height(&self) -> usize {
+        if self.value.is_none() {
+            return 0;
+        }
+        let left_height = self.left.as_ref().map_or(0, |node| node.height());
+        let right_height = self.right.as_ref().map_or(0, |node| node.height());
+        1 + left_height.max(right_height)
+    }
+
+    /// Returns the size and shape of this tree, e.g. to compare insertion
+    /// orders
+    pub fn stats(&self) -> TreeStats {
+        let mut stats = TreeStats {
+            node_count: self.len(),
+            height: 0,
+            leaf_count: 0,
+            min_leaf_depth: usize::MAX,
+            max_leaf_depth: 0,
+            average_leaf_depth: 0.0,
+        };
+        if self.value.is_none() {
+            stats.min_leaf_depth = 0;
+            return stats;
+        }
+        let mut total_leaf_depth = 0;
+        self.collect_leaf_depths(0, &mut stats, &mut total_leaf_depth);
+        stats.height = stats.max_leaf_depth + 1;
+        stats.average_leaf_depth = total_leaf_depth as f64 / stats.leaf_count as f64;
+        stats
+    }
+
+    /// Adds the leaves of this subtree at depth to stats, and their depths to
+    /// total
+    fn collect_leaf_depths(&self, depth: usize, stats: &mut TreeStats, total: &mut usize) {
+        if self.left.is_none() && self.right.is_none() {
+            stats.leaf_count += 1;
+            stats.min_leaf_depth = stats.min_leaf_depth.min(depth);
+            stats.max_leaf_depth = stats.max_leaf_depth.max(depth);
+            *total += depth;
+        }
+        for node in [&self.left, &self.right].into_iter().flatten() {
+            node.collect_leaf_depths(depth + 1, stats, total);
+        }
+    }
+
+    /// Checks the invariants of this tree: the values are in strictly
+    /// increasing order and every node stores the correct subtree size
+    pub fn is_valid(&self) -> bool {
+        match &self.value {
+            Some(_) => self.is_valid_subtree(None, None),
+            None => self.left.is_none() && self.right.is_none() && self.size == 0,
+        }
+    }
+
+    /// Checks that every value of this subtree lies strictly between lower
+    /// and upper and that all of its nodes, which must not be empty, store
+    /// the correct subtree size
+    fn is_valid_subtree(&self, lower: Option<&T>, upper: Option<&T>) -> bool {
+        let key = match &self.value {
+            Some(key) => key,
+            None => return false,
+        };
+        if lower.is_some_and(|lower| key <= lower) || upper.is_some_and(|upper| key >= upper) {
+            return false;
+        }
+        let left_size = self.left.as_ref().map_or(0, |node| node.size);
+        let right_size = self.right.as_ref().map_or(0, |node| node.size);
+        self.size == left_size + right_size + 1
+            && self
+                .left
+                .as_ref()
+                .is_none_or(|node| node.is_valid_subtree(lower, Some(key)))
+            && self
+                .right
+                .as_ref()
+                .is_none_or(|node| node.is_valid_subtree(Some(key), upper))
+    }
+
+    /// Returns true if the heights of the two subtrees of every node differ
+    /// by at most one
+    pub fn is_balanced(&self) -> bool {
+        self.balanced_height().is_some()
+    }
+
+    /// Returns the height of this subtree if it is balanced
+    fn balanced_height(&self) -> Option<usize> {
+        if self.value.is_none() {
+            return Some(0);
         }
+        let left_height = match &self.left {
+            Some(node) => node.balanced_height()?,
+            None => 0,
+        };
+        let right_height = match &self.right {
+            Some(node) => node.balanced_height()?,
+            None => 0,
+        };
+        (left_height.abs_diff(right_height) <= 1).then(|| 1 + left_height.max(right_height))
     }
 
     /// Find a value in this tree. Returns True if value is in this
@@ -64,37 +273,161 @@ where
         }
     }
 
+    /// Returns the number of edges between the root and the node holding
+    /// value, which is one less than the number of comparisons needed to
+    /// find it, or None if value is not in this tree
+    pub fn depth_of(&self, value: &T) -> Option<usize> {
+        let mut node = self;
+        let mut depth = 0;
+        loop {
+            let next = match value.cmp(node.value.as_ref()?) {
+                Ordering::Equal => return Some(depth),
+                Ordering::Less => &node.left,
+                Ordering::Greater => &node.right,
+            };
+            node = next.as_deref()?;
+            depth += 1;
+        }
+    }
+
     /// Returns a new iterator which iterates over this tree in order
     pub fn iter(&self) -> impl Iterator<Item = &T> {
         BinarySearchTreeIter::new(self)
     }
 
-    /// Insert a value into the appropriate location in this tree.
-    pub fn insert(&mut self, value: T) {
-        if self.value.is_none() {
-            self.value = Some(value);
-        } else {
-            match &self.value {
-                None => (),
-                Some(key) => {
-                    let target_node = if value < *key {
-                        &mut self.left
-                    } else {
-                        &mut self.right
-                    };
-                    match target_node {
-                        Some(ref mut node) => {
-                            node.insert(value);
-                        }
-                        None => {
-                            let mut node = BinarySearchTree::new();
-                            node.insert(value);
-                            *target_node = Some(Box::new(node));
-                        }
-                    }
+    /// Returns a new iterator which iterates in order over the values v in
+    /// this tree with lo <= v <= hi. Subtrees lying entirely outside of the
+    /// range are never visited.
+    pub fn range<'a>(&'a self, lo: &T, hi: &T) -> impl Iterator<Item = &'a T> {
+        BinarySearchTreeRangeIter::new(self, lo, hi)
+    }
+
+    /// Draws k distinct values of this tree uniformly at random, using
+    /// reservoir sampling over the iterator: the first k values fill the
+    /// sample, and then the i-th value (counting from 0) replaces a random
+    /// member of the sample with probability k / (i + 1). This needs a
+    /// single pass and does not rely on len(). If k >= len(), all values
+    /// are returned in order.
+    pub fn sample<R: Rng>(&self, k: usize, rng: &mut R) -> Vec<&T> {
+        let mut sample = Vec::with_capacity(k.min(self.len()));
+        for (i, value) in self.iter().enumerate() {
+            if i < k {
+                sample.push(value);
+            } else {
+                let j = rng.gen_range(0..i as u64 + 1) as usize;
+                if j < k {
+                    sample[j] = value;
                 }
             }
         }
+        sample
+    }
+
+    /// Returns a sorted Vec of all values in this tree. Same as
+    /// `iter().cloned().collect()`, but it allocates the Vec up front and walks
+    /// the tree recursively instead of going through the iterator's stack.
+    pub fn to_sorted_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        let mut values = Vec::with_capacity(self.len());
+        self.push_in_order(&mut values);
+        values
+    }
+
+    /// Returns the values of this tree level by level, starting with the
+    /// root. Each level lists its values from left to right, and there are
+    /// as many levels as the height of the tree.
+    pub fn level_order(&self) -> Vec<Vec<&T>> {
+        let mut levels = Vec::new();
+        if self.value.is_none() {
+            return levels;
+        }
+        let mut queue = VecDeque::from([self]);
+        while !queue.is_empty() {
+            let mut level = Vec::with_capacity(queue.len());
+            for _ in 0..queue.len() {
+                let node = queue.pop_front().unwrap();
+                level.extend(&node.value);
+                queue.extend(node.left.as_deref());
+                queue.extend(node.right.as_deref());
+            }
+            levels.push(level);
+        }
+        levels
+    }
+
+    /// Renders this tree sideways, one value per line: the root is not
+    /// indented, each level below it is indented four more spaces, and the
+    /// right subtree of a node is printed above it and the left one below.
+    /// Every value is followed by the height of its subtree and its balance
+    /// factor, i.e. the height of the right subtree minus that of the left
+    /// one.
+    pub fn to_pretty_string(&self) -> String
+    where
+        T: Debug,
+    {
+        let mut lines = Vec::with_capacity(self.len());
+        if self.value.is_some() {
+            self.render(0, &mut lines);
+        }
+        lines.concat()
+    }
+
+    /// Appends the lines of this subtree at depth to lines, returning its
+    /// height
+    fn render(&self, depth: usize, lines: &mut Vec<String>) -> usize
+    where
+        T: Debug,
+    {
+        let right_height = self
+            .right
+            .as_ref()
+            .map_or(0, |node| node.render(depth + 1, lines));
+        // The line of this node is filled in once the height of the left
+        // subtree is known
+        let line = lines.len();
+        lines.push(String::new());
+        let left_height = self
+            .left
+            .as_ref()
+            .map_or(0, |node| node.render(depth + 1, lines));
+        let height = 1 + left_height.max(right_height);
+        let balance = right_height as isize - left_height as isize;
+        lines[line] = format!(
+            "{:indent$}{:?} (height {}, balance {})\n",
+            "",
+            self.value.as_ref().unwrap(),
+            height,
+            balance,
+            indent = 4 * depth
+        );
+        height
+    }
+
+    /// Appends the values of this subtree to values in order
+    fn push_in_order(&self, values: &mut Vec<T>)
+    where
+        T: Clone,
+    {
+        if let Some(node) = &self.left {
+            node.push_in_order(values);
+        }
+        if let Some(value) = &self.value {
+            values.push(value.clone());
+        }
+        if let Some(node) = &self.right {
+            node.push_in_order(values);
+        }
+    }
+
+    /// Returns the node reached by taking the given turns from this node,
+    /// true meaning right
+    fn follow(&self, path: &[bool]) -> &BinarySearchTree<T> {
+        path.iter().fold(self, |node, &right| {
+            let child = if right { &node.right } else { &node.left };
+            child.as_deref().unwrap()
+        })
     }
 
     /// Returns the smallest value in this tree
@@ -179,164 +512,1999 @@ where
             None => None,
         }
     }
-}
 
-struct BinarySearchTreeIter<'a, T>
-where
-    T: Ord,
-{
-    stack: Vec<&'a BinarySearchTree<T>>,
-}
+    /// Returns the largest value in this tree strictly smaller than value
+    pub fn predecessor(&self, value: &T) -> Option<&T> {
+        match &self.value {
+            Some(key) => {
+                match key.cmp(value) {
+                    Ordering::Greater | Ordering::Equal => {
+                        // key >= value
+                        match &self.left {
+                            Some(node) => node.predecessor(value),
+                            None => None,
+                        }
+                    }
+                    Ordering::Less => {
+                        // key < value
+                        match &self.right {
+                            Some(node) => {
+                                let val = node.predecessor(value);
+                                match val {
+                                    Some(_) => val,
+                                    None => Some(key),
+                                }
+                            }
+                            None => Some(key),
+                        }
+                    }
+                }
+            }
+            None => None,
+        }
+    }
 
-impl<'a, T> BinarySearchTreeIter<'a, T>
-where
-    T: Ord,
-{
-    pub fn new(tree: &BinarySearchTree<T>) -> BinarySearchTreeIter<T> {
-        let mut iter = BinarySearchTreeIter { stack: vec![tree] };
-        iter.stack_push_left();
-        iter
+    /// Returns the smallest value in this tree strictly larger than value
+    pub fn successor(&self, value: &T) -> Option<&T> {
+        match &self.value {
+            Some(key) => {
+                match key.cmp(value) {
+                    Ordering::Less | Ordering::Equal => {
+                        // key <= value
+                        match &self.right {
+                            Some(node) => node.successor(value),
+                            None => None,
+                        }
+                    }
+                    Ordering::Greater => {
+                        // key > value
+                        match &self.left {
+                            Some(node) => {
+                                let val = node.successor(value);
+                                match val {
+                                    Some(_) => val,
+                                    None => Some(key),
+                                }
+                            }
+                            None => Some(key),
+                        }
+                    }
+                }
+            }
+            None => None,
+        }
     }
 
-    fn stack_push_left(&mut self) {
-        while let Some(child) = &self.stack.last().unwrap().left {
-            self.stack.push(child);
+    /// Returns the number of values in this tree smaller than value.
+    /// The value itself does not need to be in the tree.
+    pub fn rank(&self, value: &T) -> usize {
+        match &self.value {
+            Some(key) => {
+                let left_size = self.left.as_ref().map_or(0, |node| node.size);
+                match key.cmp(value) {
+                    Ordering::Greater => {
+                        // key > value
+                        self.left.as_ref().map_or(0, |node| node.rank(value))
+                    }
+                    Ordering::Less => {
+                        // key < value
+                        left_size + 1 + self.right.as_ref().map_or(0, |node| node.rank(value))
+                    }
+                    Ordering::Equal => left_size,
+                }
+            }
+            None => 0,
+        }
+    }
+
+    /// Returns the k-th smallest value in this tree, counting from 0
+    pub fn select(&self, k: usize) -> Option<&T> {
+        if k >= self.size {
+            return None;
+        }
+        let left_size = self.left.as_ref().map_or(0, |node| node.size);
+        match k.cmp(&left_size) {
+            Ordering::Less => self.left.as_ref()?.select(k),
+            Ordering::Equal => self.value.as_ref(),
+            Ordering::Greater => self.right.as_ref()?.select(k - left_size - 1),
         }
     }
 }
 
-impl<'a, T> Iterator for BinarySearchTreeIter<'a, T>
+/// The methods which change a tree, and therefore copy the nodes it shares
+/// with its clones
+impl<T> BinarySearchTree<T>
 where
-    T: Ord,
+    T: Ord + Clone,
 {
-    type Item = &'a T;
+    /// Returns a balanced tree holding the values present in either tree,
+    /// in O(n + m)
+    pub fn union(self, other: BinarySearchTree<T>) -> BinarySearchTree<T> {
+        self.merge(other, true, true, true)
+    }
 
-    fn next(&mut self) -> Option<&'a T> {
-        if self.stack.is_empty() {
-            None
-        } else {
-            let node = self.stack.pop().unwrap();
-            if node.right.is_some() {
-                self.stack.push(node.right.as_ref().unwrap().deref());
-                self.stack_push_left();
-            }
-            node.value.as_ref()
+    /// Returns a balanced tree holding the values present in both trees,
+    /// in O(n + m)
+    pub fn intersection(self, other: BinarySearchTree<T>) -> BinarySearchTree<T> {
+        self.merge(other, false, true, false)
+    }
+
+    /// Returns a balanced tree holding the values present in this tree but
+    /// not in other, in O(n + m)
+    pub fn difference(self, other: BinarySearchTree<T>) -> BinarySearchTree<T> {
+        self.merge(other, true, false, false)
+    }
+
+    /// Insert many values at once, rebalancing the tree only once.
+    ///
+    /// The new values are sorted and merged with the values of this tree,
+    /// which are then bulk-loaded into a balanced tree. This takes
+    /// O(n + k log k) for k new values, compared to O(k h) for inserting
+    /// them one at a time into a tree of height h, which for an unbalanced
+    /// tree may be as large as n + k. As with insert, values already present
+    /// are kept, and of several equal new values only the first is inserted.
+    pub fn insert_many(&mut self, values: impl IntoIterator<Item = T>) {
+        let mut values: Vec<T> = values.into_iter().collect();
+        if values.is_empty() {
+            return;
         }
+        // sort is stable, so dedup keeps the first of each run of equal values
+        values.sort();
+        dedup_sorted(&mut values);
+        let tree = std::mem::take(self);
+        *self = tree.union(BinarySearchTree::from_sorted(values));
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::BinarySearchTree;
+    /// Removes all values for which predicate returns false, and rebalances
+    /// the tree.
+    ///
+    /// The retained values are collected in order and bulk-loaded into a new
+    /// balanced tree, so this takes O(n) time and O(n) extra space no matter
+    /// how many values are removed.
+    pub fn retain(&mut self, predicate: impl Fn(&T) -> bool) {
+        let values = std::mem::take(self)
+            .into_iter()
+            .filter(|value| predicate(value))
+            .collect();
+        *self = BinarySearchTree::from_sorted(values);
+    }
 
-    fn prequel_memes_tree() -> BinarySearchTree<&'static str> {
-        let mut tree = BinarySearchTree::new();
-        tree.insert("hello there");
-        tree.insert("general kenobi");
-        tree.insert("you are a bold one");
-        tree.insert("kill him");
-        tree.insert("back away...I will deal with this jedi slime myself");
-        tree.insert("your move");
-        tree.insert("you fool");
-        tree
+    /// Splits this tree in two: the values less than value stay in this tree,
+    /// and the values greater than or equal to it are moved into the
+    /// returned tree. Both trees are rebuilt balanced from the sorted values,
+    /// which takes O(n).
+    pub fn split_off(&mut self, value: &T) -> BinarySearchTree<T> {
+        let mut values: Vec<T> = std::mem::take(self).into_iter().collect();
+        let split = values.partition_point(|v| v < value);
+        let upper = values.split_off(split);
+        *self = BinarySearchTree::from_sorted(values);
+        BinarySearchTree::from_sorted(upper)
     }
 
-    #[test]
-    fn test_search() {
-        let tree = prequel_memes_tree();
-        assert!(tree.search(&"hello there"));
-        assert!(tree.search(&"you are a bold one"));
-        assert!(tree.search(&"general kenobi"));
-        assert!(tree.search(&"you fool"));
-        assert!(tree.search(&"kill him"));
-        assert!(
-            !tree.search(&"but i was going to tosche station to pick up some power converters",)
-        );
-        assert!(!tree.search(&"only a sith deals in absolutes"));
-        assert!(!tree.search(&"you underestimate my power"));
+    /// Moves all values of other into this tree, leaving other empty. Values
+    /// present in both trees are kept once, as the value of this tree. Like
+    /// union, this merges the sorted values and bulk-loads a balanced tree
+    /// in O(n + m).
+    pub fn append(&mut self, other: &mut BinarySearchTree<T>) {
+        let other = std::mem::take(other);
+        *self = std::mem::take(self).union(other);
     }
 
-    #[test]
-    fn test_maximum_and_minimum() {
-        let tree = prequel_memes_tree();
-        assert_eq!(*tree.maximum().unwrap(), "your move");
-        assert_eq!(
-            *tree.minimum().unwrap(),
-            "back away...I will deal with this jedi slime myself"
-        );
-        let mut tree2: BinarySearchTree<i32> = BinarySearchTree::new();
-        assert!(tree2.maximum().is_none());
-        assert!(tree2.minimum().is_none());
-        tree2.insert(0);
-        assert_eq!(*tree2.minimum().unwrap(), 0);
-        assert_eq!(*tree2.maximum().unwrap(), 0);
-        tree2.insert(-5);
-        assert_eq!(*tree2.minimum().unwrap(), -5);
-        assert_eq!(*tree2.maximum().unwrap(), 0);
-        tree2.insert(5);
-        assert_eq!(*tree2.minimum().unwrap(), -5);
-        assert_eq!(*tree2.maximum().unwrap(), 5);
+    /// Merges the sorted values of both trees, keeping the values found only
+    /// in self, in both trees, or only in other, then bulk-loads the result
+    fn merge(
+        self,
+        other: BinarySearchTree<T>,
+        keep_self_only: bool,
+        keep_both: bool,
+        keep_other_only: bool,
+    ) -> BinarySearchTree<T> {
+        let mut values = Vec::with_capacity(self.len() + other.len());
+        let mut left = self.into_iter().peekable();
+        let mut right = other.into_iter().peekable();
+        loop {
+            let ordering = match (left.peek(), right.peek()) {
+                (Some(a), Some(b)) => a.cmp(b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => break,
+            };
+            match ordering {
+                Ordering::Less => {
+                    let value = left.next().unwrap();
+                    if keep_self_only {
+                        values.push(value);
+                    }
+                }
+                Ordering::Greater => {
+                    let value = right.next().unwrap();
+                    if keep_other_only {
+                        values.push(value);
+                    }
+                }
+                Ordering::Equal => {
+                    let value = left.next().unwrap();
+                    right.next();
+                    if keep_both {
+                        values.push(value);
+                    }
+                }
+            }
+        }
+        BinarySearchTree::from_sorted(values)
     }
 
-    #[test]
-    fn test_floor_and_ceil() {
-        let tree = prequel_memes_tree();
-        assert_eq!(*tree.floor(&"hello there").unwrap(), "hello there");
-        assert_eq!(
-            *tree
+    /// Insert a value into the appropriate location in this tree.
+    /// Values already present in the tree are not inserted again, so this
+    /// returns true if the value was not already present.
+    ///
+    /// The value is looked up through entry before anything is changed, so
+    /// inserting a present value copies none of the nodes shared with clones.
+    pub fn insert(&mut self, value: T) -> bool {
+        match self.entry(value) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(entry) => {
+                entry.insert();
+                true
+            }
+        }
+    }
+
+    /// Insert a value into this tree if it is not already present, and
+    /// return a reference to the value stored in the tree. If an equal value
+    /// was already present, it is kept and value is dropped.
+    pub fn get_or_insert(&mut self, value: T) -> &T {
+        self.entry(value).or_insert()
+    }
+
+    /// Looks up value in this tree and returns an entry, which can insert it
+    /// if it is absent without comparing it against the tree again.
+    ///
+    /// The entry remembers the path of the search, so inserting only walks
+    /// down that path once more to update the sizes of the subtrees.
+    pub fn entry(&mut self, value: T) -> Entry<'_, T> {
+        let mut path = Vec::new();
+        let mut node = &*self;
+        let found = loop {
+            let ordering = match &node.value {
+                Some(key) => value.cmp(key),
+                None => break false,
+            };
+            let next = match ordering {
+                Ordering::Equal => break true,
+                Ordering::Less => &node.left,
+                Ordering::Greater => &node.right,
+            };
+            path.push(ordering == Ordering::Greater);
+            match next {
+                Some(child) => node = child,
+                None => break false,
+            }
+        };
+        if found {
+            Entry::Occupied(OccupiedEntry {
+                value: self.follow(&path).value.as_ref().unwrap(),
+            })
+        } else {
+            Entry::Vacant(VacantEntry {
+                tree: self,
+                path,
+                value,
+            })
+        }
+    }
+
+    /// Removes the smallest value from this tree and returns it
+    pub fn pop_min(&mut self) -> Option<T> {
+        self.take_minimum()
+    }
+
+    /// Removes the largest value from this tree and returns it
+    pub fn pop_max(&mut self) -> Option<T> {
+        self.take_maximum()
+    }
+
+    /// Remove a value from this tree, if it is present, and return the
+    /// value which was stored in the tree.
+    pub fn remove(&mut self, value: &T) -> Option<T> {
+        self.remove_value(value)
+    }
+
+    /// Returns a new version of this tree with value inserted, leaving this
+    /// tree untouched, e.g. to keep earlier versions around as an undo stack.
+    ///
+    /// The new version starts out as an O(1) clone, and inserting copies only
+    /// the nodes on the search path (path copying): every other subtree is
+    /// shared between the versions. Each version thus costs O(h) time and
+    /// space for a tree of height h.
+    pub fn insert_persistent(&self, value: T) -> BinarySearchTree<T> {
+        let mut tree = self.clone();
+        tree.insert(value);
+        tree
+    }
+
+    /// Returns a new version of this tree with value removed, leaving this
+    /// tree untouched. Like insert_persistent, this copies only the nodes on
+    /// the path to the removed value and to its successor.
+    pub fn remove_persistent(&self, value: &T) -> BinarySearchTree<T> {
+        let mut tree = self.clone();
+        tree.remove_value(value);
+        tree
+    }
+
+    /// Removes a value from this tree, returning it if it was present.
+    ///
+    /// This walks down the tree in a loop rather than recursively, so it
+    /// does not use stack space proportional to the height of the tree.
+    /// The first walk only records the search path, so removing an absent
+    /// value copies none of the nodes shared with clones of the tree; the
+    /// second walk follows that path to decrement the sizes of the subtrees.
+    fn remove_value(&mut self, value: &T) -> Option<T> {
+        let mut path = Vec::new();
+        let mut node = &*self;
+        loop {
+            let ordering = node.value.as_ref()?.cmp(value);
+            let next = match ordering {
+                Ordering::Equal => break,
+                Ordering::Greater => &node.left,
+                Ordering::Less => &node.right,
+            };
+            path.push(ordering == Ordering::Less);
+            node = next.as_deref()?;
+        }
+        let Some((&last, path)) = path.split_last() else {
+            return self.remove_root();
+        };
+        let mut node = self;
+        for &right in path {
+            node.size -= 1;
+            let child = if right {
+                &mut node.right
+            } else {
+                &mut node.left
+            };
+            node = Arc::make_mut(child.as_mut().unwrap());
+        }
+        node.size -= 1;
+        let target_node = if last {
+            &mut node.right
+        } else {
+            &mut node.left
+        };
+        let node = Arc::make_mut(target_node.as_mut().unwrap());
+        let removed = node.remove_root();
+        if node.value.is_none() {
+            *target_node = None;
+        }
+        removed
+    }
+
+    /// Removes the value stored at the root of this subtree and returns it.
+    /// A subtree whose last value was removed is left with `value == None`,
+    /// so that the parent can unlink it.
+    fn remove_root(&mut self) -> Option<T> {
+        match (self.left.take(), self.right.take()) {
+            (None, None) => {
+                self.size = 0;
+                self.value.take()
+            }
+            (Some(child), None) | (None, Some(child)) => {
+                std::mem::replace(self, Arc::unwrap_or_clone(child)).value
+            }
+            (Some(left), Some(mut right)) => {
+                // Replace the value with its in-order successor, which is
+                // detached in the same walk that finds it
+                let successor = Arc::make_mut(&mut right).take_minimum();
+                let removed = std::mem::replace(&mut self.value, successor);
+                self.left = Some(left);
+                if right.value.is_some() {
+                    self.right = Some(right);
+                }
+                self.size -= 1;
+                removed
+            }
+        }
+    }
+
+    /// Detaches the smallest value from this subtree and returns it
+    fn take_minimum(&mut self) -> Option<T> {
+        if self.left.is_none() {
+            let minimum = self.value.take();
+            match self.right.take() {
+                Some(node) => *self = Arc::unwrap_or_clone(node),
+                None => self.size = 0,
+            }
+            return minimum;
+        }
+        self.size -= 1;
+        let mut target_node = &mut self.left;
+        while target_node.as_ref().unwrap().left.is_some() {
+            let node = Arc::make_mut(target_node.as_mut().unwrap());
+            node.size -= 1;
+            target_node = &mut node.left;
+        }
+        let node = Arc::unwrap_or_clone(target_node.take().unwrap());
+        *target_node = node.right;
+        node.value
+    }
+
+    /// Detaches the largest value from this subtree and returns it
+    fn take_maximum(&mut self) -> Option<T> {
+        if self.right.is_none() {
+            let maximum = self.value.take();
+            match self.left.take() {
+                Some(node) => *self = Arc::unwrap_or_clone(node),
+                None => self.size = 0,
+            }
+            return maximum;
+        }
+        self.size -= 1;
+        let mut target_node = &mut self.right;
+        while target_node.as_ref().unwrap().right.is_some() {
+            let node = Arc::make_mut(target_node.as_mut().unwrap());
+            node.size -= 1;
+            target_node = &mut node.right;
+        }
+        let node = Arc::unwrap_or_clone(target_node.take().unwrap());
+        *target_node = node.left;
+        node.value
+    }
+}
+
+/// The size and shape of a tree, as returned by BinarySearchTree::stats.
+/// Depths count the edges from the root, and a leaf is a node without
+/// children. All fields are zero for an empty tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeStats {
+    pub node_count: usize,
+    pub height: usize,
+    pub leaf_count: usize,
+    pub min_leaf_depth: usize,
+    pub max_leaf_depth: usize,
+    pub average_leaf_depth: f64,
+}
+
+/// Removes consecutive duplicates from v in place, keeping the first of each
+/// run of equal values. For a sorted vector this leaves every value exactly
+/// once, which is what `BinarySearchTree::from_sorted` requires.
+pub fn dedup_sorted<T: PartialEq>(v: &mut Vec<T>) {
+    v.dedup();
+}
+
+/// A BST is serialized as the sequence of its values in order
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for BinarySearchTree<T>
+where
+    T: Ord + serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeSeq;
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for value in self.iter() {
+            seq.serialize_element(value)?;
+        }
+        seq.end()
+    }
+}
+
+/// A BST is deserialized from a sequence of values in any order, and is
+/// rebuilt as a balanced tree
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for BinarySearchTree<T>
+where
+    T: Ord + serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut values = Vec::<T>::deserialize(deserializer)?;
+        values.sort();
+        dedup_sorted(&mut values);
+        Ok(BinarySearchTree::from_sorted(values))
+    }
+}
+
+/// A view into a single value of a BinarySearchTree, which is either present
+/// or absent. It is returned by BinarySearchTree::entry.
+pub enum Entry<'a, T>
+where
+    T: Ord,
+{
+    Occupied(OccupiedEntry<'a, T>),
+    Vacant(VacantEntry<'a, T>),
+}
+
+/// A value which is present in the tree
+pub struct OccupiedEntry<'a, T>
+where
+    T: Ord,
+{
+    value: &'a T,
+}
+
+/// A value which is absent from the tree, together with the place where it
+/// belongs
+pub struct VacantEntry<'a, T>
+where
+    T: Ord,
+{
+    tree: &'a mut BinarySearchTree<T>,
+    /// The turns taken from the root to the empty child slot, true meaning
+    /// right
+    path: Vec<bool>,
+    value: T,
+}
+
+impl<'a, T> Entry<'a, T>
+where
+    T: Ord,
+{
+    /// Inserts the value if it is absent, and returns a reference to the
+    /// value stored in the tree
+    pub fn or_insert(self) -> &'a T
+    where
+        T: Clone,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.get(),
+            Entry::Vacant(entry) => entry.insert(),
+        }
+    }
+
+    /// Returns true if the value is present in the tree
+    pub fn is_occupied(&self) -> bool {
+        matches!(self, Entry::Occupied(_))
+    }
+}
+
+impl<'a, T> OccupiedEntry<'a, T>
+where
+    T: Ord,
+{
+    /// Returns the value stored in the tree
+    pub fn get(&self) -> &'a T {
+        self.value
+    }
+}
+
+impl<'a, T> VacantEntry<'a, T>
+where
+    T: Ord,
+{
+    /// Returns the value which would be inserted
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+
+    /// Takes back the value without inserting it
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// Inserts the value and returns a reference to it
+    pub fn insert(self) -> &'a T
+    where
+        T: Clone,
+    {
+        let VacantEntry { tree, path, value } = self;
+        let mut node = tree;
+        for &right in &path {
+            node.size += 1;
+            let child = if right {
+                &mut node.right
+            } else {
+                &mut node.left
+            };
+            node = Arc::make_mut(child.get_or_insert_with(|| Arc::new(BinarySearchTree::new())));
+        }
+        node.size = 1;
+        node.value.insert(value)
+    }
+}
+
+struct BinarySearchTreeIter<'a, T>
+where
+    T: Ord,
+{
+    stack: Vec<&'a BinarySearchTree<T>>,
+}
+
+impl<'a, T> BinarySearchTreeIter<'a, T>
+where
+    T: Ord,
+{
+    pub fn new(tree: &BinarySearchTree<T>) -> BinarySearchTreeIter<T> {
+        let mut iter = BinarySearchTreeIter { stack: vec![tree] };
+        iter.stack_push_left();
+        iter
+    }
+
+    fn stack_push_left(&mut self) {
+        while let Some(child) = &self.stack.last().unwrap().left {
+            self.stack.push(child);
+        }
+    }
+}
+
+impl<'a, T> Iterator for BinarySearchTreeIter<'a, T>
+where
+    T: Ord,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.stack.is_empty() {
+            None
+        } else {
+            let node = self.stack.pop().unwrap();
+            if node.right.is_some() {
+                self.stack.push(node.right.as_ref().unwrap().deref());
+                self.stack_push_left();
+            }
+            node.value.as_ref()
+        }
+    }
+}
+
+impl<T> IntoIterator for BinarySearchTree<T>
+where
+    T: Ord + Clone,
+{
+    type Item = T;
+    type IntoIter = BinarySearchTreeIntoIter<T>;
+
+    /// Consumes this tree, returning an iterator over its values in order
+    fn into_iter(self) -> Self::IntoIter {
+        BinarySearchTreeIntoIter::new(self)
+    }
+}
+
+/// An iterator which moves the values out of a BST in order
+pub struct BinarySearchTreeIntoIter<T>
+where
+    T: Ord,
+{
+    stack: Vec<BinarySearchTree<T>>,
+}
+
+impl<T> BinarySearchTreeIntoIter<T>
+where
+    T: Ord + Clone,
+{
+    fn new(tree: BinarySearchTree<T>) -> BinarySearchTreeIntoIter<T> {
+        let mut iter = BinarySearchTreeIntoIter { stack: Vec::new() };
+        iter.stack_push_left(tree);
+        iter
+    }
+
+    fn stack_push_left(&mut self, mut node: BinarySearchTree<T>) {
+        while let Some(child) = node.left.take() {
+            self.stack.push(node);
+            node = Arc::unwrap_or_clone(child);
+        }
+        self.stack.push(node);
+    }
+}
+
+impl<T> Iterator for BinarySearchTreeIntoIter<T>
+where
+    T: Ord + Clone,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let mut node = self.stack.pop()?;
+        if let Some(child) = node.right.take() {
+            self.stack_push_left(Arc::unwrap_or_clone(child));
+        }
+        node.value
+    }
+}
+
+struct BinarySearchTreeRangeIter<'a, T>
+where
+    T: Ord,
+{
+    stack: Vec<&'a BinarySearchTree<T>>,
+    /// The largest value in the tree which is still within the range
+    last: Option<&'a T>,
+}
+
+impl<'a, T> BinarySearchTreeRangeIter<'a, T>
+where
+    T: Ord,
+{
+    fn new(tree: &'a BinarySearchTree<T>, lo: &T, hi: &T) -> BinarySearchTreeRangeIter<'a, T> {
+        let mut iter = BinarySearchTreeRangeIter {
+            stack: Vec::new(),
+            last: None,
+        };
+        if lo > hi {
+            return iter;
+        }
+        iter.last = tree.floor(hi);
+        // Only keep the nodes with key >= lo on the stack, skipping the left
+        // subtrees of all the others
+        let mut current = Some(tree);
+        while let Some(node) = current {
+            current = match &node.value {
+                Some(key) if key >= lo => {
+                    iter.stack.push(node);
+                    node.left.as_deref()
+                }
+                Some(_) => node.right.as_deref(),
+                None => None,
+            };
+        }
+        iter
+    }
+
+    fn stack_push_left(&mut self, mut node: &'a BinarySearchTree<T>) {
+        self.stack.push(node);
+        while let Some(child) = &node.left {
+            node = child;
+            self.stack.push(node);
+        }
+    }
+}
+
+impl<'a, T> Iterator for BinarySearchTreeRangeIter<'a, T>
+where
+    T: Ord,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.stack.pop()?;
+        let value = node.value.as_ref()?;
+        match self.last {
+            Some(last) if value <= last => {
+                if let Some(right) = &node.right {
+                    self.stack_push_left(right);
+                }
+                Some(value)
+            }
+            _ => {
+                self.stack.clear();
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{dedup_sorted, BinarySearchTree, TreeStats};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use std::sync::Arc;
+
+    fn prequel_memes_tree() -> BinarySearchTree<&'static str> {
+        let mut tree = BinarySearchTree::new();
+        tree.insert("hello there");
+        tree.insert("general kenobi");
+        tree.insert("you are a bold one");
+        tree.insert("kill him");
+        tree.insert("back away...I will deal with this jedi slime myself");
+        tree.insert("your move");
+        tree.insert("you fool");
+        tree
+    }
+
+    #[test]
+    fn test_search() {
+        let tree = prequel_memes_tree();
+        assert!(tree.search(&"hello there"));
+        assert!(tree.search(&"you are a bold one"));
+        assert!(tree.search(&"general kenobi"));
+        assert!(tree.search(&"you fool"));
+        assert!(tree.search(&"kill him"));
+        assert!(
+            !tree.search(&"but i was going to tosche station to pick up some power converters",)
+        );
+        assert!(!tree.search(&"only a sith deals in absolutes"));
+        assert!(!tree.search(&"you underestimate my power"));
+    }
+
+    #[test]
+    fn test_maximum_and_minimum() {
+        let tree = prequel_memes_tree();
+        assert_eq!(*tree.maximum().unwrap(), "your move");
+        assert_eq!(
+            *tree.minimum().unwrap(),
+            "back away...I will deal with this jedi slime myself"
+        );
+        let mut tree2: BinarySearchTree<i32> = BinarySearchTree::new();
+        assert!(tree2.maximum().is_none());
+        assert!(tree2.minimum().is_none());
+        tree2.insert(0);
+        assert_eq!(*tree2.minimum().unwrap(), 0);
+        assert_eq!(*tree2.maximum().unwrap(), 0);
+        tree2.insert(-5);
+        assert_eq!(*tree2.minimum().unwrap(), -5);
+        assert_eq!(*tree2.maximum().unwrap(), 0);
+        tree2.insert(5);
+        assert_eq!(*tree2.minimum().unwrap(), -5);
+        assert_eq!(*tree2.maximum().unwrap(), 5);
+    }
+
+    #[test]
+    fn test_floor_and_ceil() {
+        let tree = prequel_memes_tree();
+        assert_eq!(*tree.floor(&"hello there").unwrap(), "hello there");
+        assert_eq!(
+            *tree
                 .floor(&"these are not the droids you're looking for")
                 .unwrap(),
             "kill him"
         );
-        assert!(tree.floor(&"another death star").is_none());
-        assert_eq!(*tree.floor(&"you fool").unwrap(), "you fool");
+        assert!(tree.floor(&"another death star").is_none());
+        assert_eq!(*tree.floor(&"you fool").unwrap(), "you fool");
+        assert_eq!(
+            *tree.floor(&"but i was going to tasche station").unwrap(),
+            "back away...I will deal with this jedi slime myself"
+        );
+        assert_eq!(
+            *tree.floor(&"you underestimate my power").unwrap(),
+            "you fool"
+        );
+        assert_eq!(*tree.floor(&"your new empire").unwrap(), "your move");
+        assert_eq!(*tree.ceil(&"hello there").unwrap(), "hello there");
+        assert_eq!(
+            *tree
+                .ceil(&"these are not the droids you're looking for")
+                .unwrap(),
+            "you are a bold one"
+        );
+        assert_eq!(
+            *tree.ceil(&"another death star").unwrap(),
+            "back away...I will deal with this jedi slime myself"
+        );
+        assert_eq!(*tree.ceil(&"you fool").unwrap(), "you fool");
+        assert_eq!(
+            *tree.ceil(&"but i was going to tasche station").unwrap(),
+            "general kenobi"
+        );
+        assert_eq!(
+            *tree.ceil(&"you underestimate my power").unwrap(),
+            "your move"
+        );
+        assert!(tree.ceil(&"your new empire").is_none());
+    }
+
+    #[test]
+    fn test_iterator() {
+        let tree = prequel_memes_tree();
+        let mut iter = tree.iter();
+        assert_eq!(
+            iter.next().unwrap(),
+            &"back away...I will deal with this jedi slime myself"
+        );
+        assert_eq!(iter.next().unwrap(), &"general kenobi");
+        assert_eq!(iter.next().unwrap(), &"hello there");
+        assert_eq!(iter.next().unwrap(), &"kill him");
+        assert_eq!(iter.next().unwrap(), &"you are a bold one");
+        assert_eq!(iter.next().unwrap(), &"you fool");
+        assert_eq!(iter.next().unwrap(), &"your move");
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_len() {
+        let mut tree = BinarySearchTree::new();
+        assert_eq!(tree.len(), 0);
+        assert!(tree.is_empty());
+        for value in [5, 3, 8, 1, 4, 7, 9] {
+            assert!(tree.insert(value));
+        }
+        assert_eq!(tree.len(), 7);
+        assert!(!tree.is_empty());
+
+        // duplicates are not inserted twice
+        assert!(!tree.insert(5));
+        assert!(!tree.insert(1));
+        assert_eq!(tree.len(), 7);
+
+        // absent values do not change the length
+        assert_eq!(tree.remove(&6), None);
+        assert_eq!(tree.len(), 7);
+
+        // leaf, node with two children, and the root
+        tree.remove(&1);
+        assert_eq!(tree.len(), 6);
+        tree.remove(&8);
+        assert_eq!(tree.len(), 5);
+        assert_eq!(tree.remove(&5), Some(5));
+        assert_eq!(tree.len(), 4);
+        assert_eq!(tree.remove(&5), None);
+        assert_eq!(tree.len(), 4);
+        assert_eq!(tree.iter().collect::<Vec<_>>(), vec![&3, &4, &7, &9]);
+
+        for value in [3, 4, 7, 9] {
+            tree.remove(&value);
+        }
+        assert_eq!(tree.len(), 0);
+        assert!(tree.is_empty());
+        assert!(tree.minimum().is_none());
+        assert!(tree.insert(2));
+        assert_eq!(tree.len(), 1);
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut tree = prequel_memes_tree();
+        tree.clear();
+        assert!(tree.is_empty());
+        assert_eq!(tree.len(), 0);
+        assert_eq!(tree.height(), 0);
+        assert!(tree.is_valid());
+        assert!(!tree.search(&"hello there"));
+        assert_eq!(tree.iter().next(), None);
+
+        assert!(tree.insert("hello there"));
+        assert!(tree.insert("general kenobi"));
+        assert_eq!(tree.len(), 2);
+        assert!(tree.is_valid());
+        assert_eq!(tree.minimum(), Some(&"general kenobi"));
+
+        let mut empty: BinarySearchTree<i32> = BinarySearchTree::new();
+        empty.clear();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut tree = prequel_memes_tree();
+        tree.remove(&"hello there");
+        tree.remove(&"kill him");
+        tree.remove(&"only a sith deals in absolutes");
+        assert!(!tree.search(&"hello there"));
+        assert!(!tree.search(&"kill him"));
+        assert_eq!(
+            tree.iter().copied().collect::<Vec<_>>(),
+            vec![
+                "back away...I will deal with this jedi slime myself",
+                "general kenobi",
+                "you are a bold one",
+                "you fool",
+                "your move",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_remove_returns_stored_value() {
+        // Keyed is ordered by key only, so the lookup value has no payload
+        let lookup = |key| Keyed { key, payload: "" };
+
+        let mut tree = BinarySearchTree::new();
+        for (key, payload) in [(4, "four"), (2, "two"), (6, "six"), (1, "one"), (5, "five")] {
+            tree.insert(Keyed { key, payload });
+        }
+        // a leaf, the root with two children, and a node with one child
+        for (key, payload) in [(5, "five"), (4, "four"), (2, "two")] {
+            let removed = tree.remove(&lookup(key)).unwrap();
+            assert_eq!(removed.payload, payload);
+            assert!(tree.is_valid());
+        }
+        assert!(tree.remove(&lookup(4)).is_none());
+        assert!(tree.remove(&lookup(3)).is_none());
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree.remove(&lookup(6)).unwrap().payload, "six");
+        assert_eq!(tree.remove(&lookup(1)).unwrap().payload, "one");
+        assert!(tree.is_empty() && tree.is_valid());
+        assert!(tree.remove(&lookup(1)).is_none());
+    }
+
+    #[test]
+    fn test_rank_and_select() {
+        let tree = prequel_memes_tree();
+        let sorted: Vec<_> = tree.iter().collect();
+        for (k, value) in sorted.iter().enumerate() {
+            assert_eq!(tree.select(k), Some(*value));
+            assert_eq!(tree.rank(value), k);
+        }
+        assert!(tree.select(sorted.len()).is_none());
+
+        for value in [
+            "another death star",
+            "hello there",
+            "these are not the droids you're looking for",
+            "you underestimate my power",
+            "your new empire",
+        ] {
+            let expected = sorted.iter().filter(|key| ***key < value).count();
+            assert_eq!(tree.rank(&value), expected);
+        }
+
+        let empty: BinarySearchTree<i32> = BinarySearchTree::new();
+        assert_eq!(empty.rank(&0), 0);
+        assert!(empty.select(0).is_none());
+    }
+
+    #[test]
+    fn test_range() {
+        let mut tree = BinarySearchTree::new();
+        for value in [50, 30, 70, 20, 40, 60, 80, 35, 45, 65] {
+            tree.insert(value);
+        }
+        // inclusive boundaries
+        assert_eq!(
+            tree.range(&35, &65).collect::<Vec<_>>(),
+            vec![&35, &40, &45, &50, &60, &65]
+        );
+        // boundaries between keys
+        assert_eq!(
+            tree.range(&36, &64).collect::<Vec<_>>(),
+            vec![&40, &45, &50, &60]
+        );
+        assert_eq!(tree.range(&50, &50).collect::<Vec<_>>(), vec![&50]);
+        assert_eq!(
+            tree.range(&0, &100).collect::<Vec<_>>(),
+            tree.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(tree.range(&81, &100).next(), None);
+        assert_eq!(tree.range(&0, &19).next(), None);
+        assert_eq!(tree.range(&51, &59).next(), None);
+        // lo > hi
+        assert_eq!(tree.range(&65, &35).next(), None);
+
+        let empty: BinarySearchTree<i32> = BinarySearchTree::new();
+        assert_eq!(empty.range(&0, &10).next(), None);
+    }
+
+    #[test]
+    fn test_from_iterator_and_extend() {
+        let tree: BinarySearchTree<i32> = vec![5, 2, 8, 2, 9, 1, 5].into_iter().collect();
+        assert_eq!(tree.len(), 5);
+        assert_eq!(tree.iter().collect::<Vec<_>>(), vec![&1, &2, &5, &8, &9]);
+
+        let mut tree: BinarySearchTree<i32> = (1..=10).collect();
+        assert_eq!(tree.len(), 10);
+        assert!(tree.iter().copied().eq(1..=10));
+
+        tree.extend(vec![8, 15, 0, 12, 15]);
+        assert_eq!(tree.len(), 13);
+        assert_eq!(
+            tree.iter().copied().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 12, 15]
+        );
+    }
+
+    #[test]
+    fn test_from_sorted() {
+        for n in [0_usize, 1, 2, 3, 4, 7, 8, 15, 16, 100, 1000] {
+            let tree = BinarySearchTree::from_sorted((0..n).collect());
+            assert_eq!(tree.len(), n);
+            assert!(tree.iter().copied().eq(0..n));
+            // ceil(log2(n + 1))
+            let expected_height = (usize::BITS - n.leading_zeros()) as usize;
+            assert_eq!(tree.height(), expected_height, "n = {n}");
+            for k in 0..n {
+                assert_eq!(tree.select(k), Some(&k));
+            }
+        }
+    }
+
+    #[test]
+    fn test_dedup_sorted() {
+        let mut v = vec![1, 1, 2, 3, 3, 3, 4, 5, 5];
+        dedup_sorted(&mut v);
+        assert_eq!(v, vec![1, 2, 3, 4, 5]);
+
+        let mut all_equal = vec![7; 10];
+        dedup_sorted(&mut all_equal);
+        assert_eq!(all_equal, vec![7]);
+
+        let mut distinct: Vec<i32> = (0..10).collect();
+        dedup_sorted(&mut distinct);
+        assert!(distinct.iter().copied().eq(0..10));
+
+        let mut empty: Vec<i32> = vec![];
+        dedup_sorted(&mut empty);
+        assert!(empty.is_empty());
+
+        // the first of each run is kept
+        let mut keyed = vec![
+            Keyed {
+                key: 1,
+                payload: "a",
+            },
+            Keyed {
+                key: 1,
+                payload: "b",
+            },
+            Keyed {
+                key: 2,
+                payload: "c",
+            },
+            Keyed {
+                key: 2,
+                payload: "d",
+            },
+            Keyed {
+                key: 2,
+                payload: "e",
+            },
+        ];
+        dedup_sorted(&mut keyed);
+        let payloads: Vec<_> = keyed.iter().map(|k| k.payload).collect();
+        assert_eq!(payloads, vec!["a", "c"]);
+    }
+
+    #[test]
+    fn test_insert_many() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut batched = BinarySearchTree::new();
+        let mut single = BinarySearchTree::new();
+        for _ in 0..20 {
+            let values: Vec<u32> = (0..rng.gen_range(0..100))
+                .map(|_| rng.gen_range(0..1000))
+                .collect();
+            batched.insert_many(values.iter().copied());
+            for &value in &values {
+                single.insert(value);
+            }
+            assert!(batched.is_valid() && batched.is_balanced());
+            assert_eq!(batched.len(), single.len());
+            assert!(batched.iter().eq(single.iter()));
+        }
+        for value in 0..1000 {
+            assert_eq!(batched.search(&value), single.search(&value));
+        }
+
+        // sorted input would make a chain out of single inserts
+        let mut tree = BinarySearchTree::new();
+        tree.insert_many(0..1023);
+        assert_eq!(tree.height(), 10);
+        tree.insert_many(Vec::new());
+        assert_eq!(tree.len(), 1023);
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut tree: BinarySearchTree<i32> = (1..=20).collect();
+        assert_eq!(tree.height(), 20);
+        tree.retain(|value| value % 2 == 0);
+        assert!(tree.is_valid() && tree.is_balanced());
+        assert_eq!(tree.len(), 10);
+        assert_eq!(tree.height(), 4);
+        for value in 1..=20 {
+            assert_eq!(tree.search(&value), value % 2 == 0, "value = {value}");
+        }
+        assert!(tree.iter().copied().eq((2..=20).step_by(2)));
+
+        tree.retain(|_| true);
+        assert_eq!(tree.len(), 10);
+        tree.retain(|_| false);
+        assert!(tree.is_empty() && tree.is_valid());
+        tree.retain(|_| true);
+        assert!(tree.is_empty());
+    }
+
+    #[test]
+    fn test_split_off() {
+        let mut lower: BinarySearchTree<i32> = (1..=20).collect();
+        let upper = lower.split_off(&8);
+        assert!(lower.is_valid() && lower.is_balanced());
+        assert!(upper.is_valid() && upper.is_balanced());
+        assert!(lower.iter().copied().eq(1..8));
+        assert!(upper.iter().copied().eq(8..=20));
+        // the split value itself goes to the returned tree
+        assert!(!lower.search(&8) && upper.search(&8));
+        assert!(lower.iter().all(|value| !upper.search(value)));
+
+        // absent split value
+        let mut lower: BinarySearchTree<i32> = (0..20).map(|value| value * 2).collect();
+        let upper = lower.split_off(&15);
+        assert_eq!(lower.maximum(), Some(&14));
+        assert_eq!(upper.minimum(), Some(&16));
+        assert_eq!(lower.len() + upper.len(), 20);
+
+        // everything or nothing moves
+        let mut tree: BinarySearchTree<i32> = (1..=5).collect();
+        let all = tree.split_off(&1);
+        assert!(tree.is_empty() && tree.is_valid());
+        assert_eq!(all.len(), 5);
+        let mut tree = all;
+        let none = tree.split_off(&6);
+        assert!(none.is_empty() && none.is_valid());
+        assert_eq!(tree.len(), 5);
+        assert!(BinarySearchTree::<i32>::new().split_off(&0).is_empty());
+    }
+
+    #[test]
+    fn test_append() {
+        // overlapping
+        let mut tree: BinarySearchTree<i32> = (0..10).collect();
+        let mut other: BinarySearchTree<i32> = (5..15).collect();
+        tree.append(&mut other);
+        assert!(tree.is_valid() && tree.is_balanced());
+        assert!(tree.iter().copied().eq(0..15));
+        assert!(other.is_empty() && other.is_valid());
+
+        // disjoint, and other lies below self
+        let mut other: BinarySearchTree<i32> = (-5..0).collect();
+        tree.append(&mut other);
+        assert!(tree.iter().copied().eq(-5..15));
+        assert_eq!(tree.len(), 20);
+        assert!(other.is_empty());
+
+        // empty trees on either side
+        tree.append(&mut other);
+        assert_eq!(tree.len(), 20);
+        let mut empty = BinarySearchTree::new();
+        empty.append(&mut tree);
+        assert_eq!(empty.len(), 20);
+        assert!(tree.is_empty());
+
+        // the value of self is kept for duplicates
+        let mut tree = BinarySearchTree::new();
+        tree.insert(Keyed {
+            key: 1,
+            payload: "self",
+        });
+        let mut other = BinarySearchTree::new();
+        other.insert(Keyed {
+            key: 1,
+            payload: "other",
+        });
+        tree.append(&mut other);
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree.minimum().unwrap().payload, "self");
+    }
+
+    #[test]
+    fn test_depth_of() {
+        let tree = prequel_memes_tree();
+        assert_eq!(tree.depth_of(&"hello there"), Some(0));
+        assert_eq!(tree.depth_of(&"general kenobi"), Some(1));
+        assert_eq!(tree.depth_of(&"kill him"), Some(2));
+        // the deepest leaf
+        assert_eq!(tree.depth_of(&"you fool"), Some(3));
+        assert_eq!(tree.depth_of(&"only a sith deals in absolutes"), None);
+        assert_eq!(tree.depth_of(&"a"), None);
+
+        let empty: BinarySearchTree<i32> = BinarySearchTree::new();
+        assert_eq!(empty.depth_of(&1), None);
+
+        // the deepest value sits one level above the height
+        let tree: BinarySearchTree<i32> = (1..=10).collect();
+        assert_eq!(tree.depth_of(&10), Some(tree.height() - 1));
+    }
+
+    #[test]
+    fn test_stats() {
+        let balanced = BinarySearchTree::from_sorted((1..=15).collect());
         assert_eq!(
-            *tree.floor(&"but i was going to tasche station").unwrap(),
-            "back away...I will deal with this jedi slime myself"
+            balanced.stats(),
+            TreeStats {
+                node_count: 15,
+                height: 4,
+                leaf_count: 8,
+                min_leaf_depth: 3,
+                max_leaf_depth: 3,
+                average_leaf_depth: 3.0,
+            }
         );
+
+        // inserting in sorted order builds a chain
+        let degenerate: BinarySearchTree<i32> = (1..=15).collect();
         assert_eq!(
-            *tree.floor(&"you underestimate my power").unwrap(),
-            "you fool"
+            degenerate.stats(),
+            TreeStats {
+                node_count: 15,
+                height: 15,
+                leaf_count: 1,
+                min_leaf_depth: 14,
+                max_leaf_depth: 14,
+                average_leaf_depth: 14.0,
+            }
         );
-        assert_eq!(*tree.floor(&"your new empire").unwrap(), "your move");
-        assert_eq!(*tree.ceil(&"hello there").unwrap(), "hello there");
+
+        // 1, 4 and 7 are leaves at depth 3, and 10 is a leaf at depth 2
+        let tree = BinarySearchTree::from_sorted((1..=10).collect());
+        let stats = tree.stats();
+        assert_eq!(stats.height, tree.height());
+        assert_eq!(stats.leaf_count, 4);
+        assert_eq!((stats.min_leaf_depth, stats.max_leaf_depth), (2, 3));
+        assert_eq!(stats.average_leaf_depth, 2.75);
+
+        let empty: BinarySearchTree<i32> = BinarySearchTree::new();
         assert_eq!(
-            *tree
-                .ceil(&"these are not the droids you're looking for")
-                .unwrap(),
-            "you are a bold one"
+            empty.stats(),
+            TreeStats {
+                node_count: 0,
+                height: 0,
+                leaf_count: 0,
+                min_leaf_depth: 0,
+                max_leaf_depth: 0,
+                average_leaf_depth: 0.0,
+            }
         );
+    }
+
+    #[test]
+    fn test_height() {
+        let mut tree = BinarySearchTree::new();
+        assert_eq!(tree.height(), 0);
+        tree.insert(2);
+        assert_eq!(tree.height(), 1);
+        tree.insert(1);
+        tree.insert(3);
+        assert_eq!(tree.height(), 2);
+        tree.insert(4);
+        tree.insert(5);
+        assert_eq!(tree.height(), 4);
+    }
+
+    #[test]
+    fn test_into_iterator() {
+        let values = vec![40, 10, 60, 5, 20, 50, 70, 15, 65];
+        let tree: BinarySearchTree<i32> = values.iter().copied().collect();
+        let mut sorted = values;
+        sorted.sort();
+        assert_eq!(tree.into_iter().collect::<Vec<_>>(), sorted);
+
+        let tree = prequel_memes_tree();
+        let expected: Vec<_> = tree.iter().copied().collect();
+        let mut drained = Vec::new();
+        for value in tree {
+            drained.push(value);
+        }
+        assert_eq!(drained, expected);
+
+        let empty: BinarySearchTree<String> = BinarySearchTree::new();
+        assert_eq!(empty.into_iter().next(), None);
+    }
+
+    #[test]
+    fn test_predecessor_and_successor() {
+        let tree: BinarySearchTree<i32> = vec![50, 30, 70, 20, 40, 60, 80].into_iter().collect();
+        // present in the middle
+        assert_eq!(tree.predecessor(&50), Some(&40));
+        assert_eq!(tree.successor(&50), Some(&60));
+        assert_eq!(tree.predecessor(&40), Some(&30));
+        assert_eq!(tree.successor(&40), Some(&50));
+        // equal to the minimum and maximum
+        assert_eq!(tree.predecessor(&20), None);
+        assert_eq!(tree.successor(&20), Some(&30));
+        assert_eq!(tree.predecessor(&80), Some(&70));
+        assert_eq!(tree.successor(&80), None);
+        // absent
+        assert_eq!(tree.predecessor(&45), Some(&40));
+        assert_eq!(tree.successor(&45), Some(&50));
+        assert_eq!(tree.predecessor(&0), None);
+        assert_eq!(tree.successor(&100), None);
+        assert_eq!(tree.predecessor(&100), Some(&80));
+        assert_eq!(tree.successor(&0), Some(&20));
+
+        let empty: BinarySearchTree<i32> = BinarySearchTree::new();
+        assert_eq!(empty.predecessor(&0), None);
+        assert_eq!(empty.successor(&0), None);
+    }
+
+    #[test]
+    fn test_clone() {
+        let mut tree: BinarySearchTree<i32> = vec![4, 2, 6, 1, 3, 5, 7].into_iter().collect();
+        let mut copy = tree.clone();
+        // the clone shares every node but the root
+        assert!(Arc::ptr_eq(
+            tree.left.as_ref().unwrap(),
+            copy.left.as_ref().unwrap()
+        ));
+        assert!(Arc::ptr_eq(
+            tree.right.as_ref().unwrap(),
+            copy.right.as_ref().unwrap()
+        ));
+
+        copy.insert(8);
+        // the left subtree was left alone, the path to 8 was copied
+        assert!(Arc::ptr_eq(
+            tree.left.as_ref().unwrap(),
+            copy.left.as_ref().unwrap()
+        ));
+        assert!(!Arc::ptr_eq(
+            tree.right.as_ref().unwrap(),
+            copy.right.as_ref().unwrap()
+        ));
+        copy.remove(&4);
+        assert!(tree.is_valid() && copy.is_valid());
+        assert_eq!(tree.len(), 7);
+        assert!(tree.iter().copied().eq(1..=7));
+        assert_eq!(copy.len(), 7);
         assert_eq!(
-            *tree.ceil(&"another death star").unwrap(),
-            "back away...I will deal with this jedi slime myself"
+            copy.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 5, 6, 7, 8]
         );
-        assert_eq!(*tree.ceil(&"you fool").unwrap(), "you fool");
+
+        tree.remove(&1);
+        assert!(copy.search(&1));
+        assert!(!tree.search(&1));
+    }
+
+    #[test]
+    fn test_eq_ignores_shape() {
+        let balanced = BinarySearchTree::from_sorted((1..=7).collect());
+        let degenerate: BinarySearchTree<i32> = (1..=7).collect();
+        assert_ne!(balanced.height(), degenerate.height());
+        assert_eq!(balanced, degenerate);
+        assert_ne!(balanced, BinarySearchTree::from_sorted((1..=6).collect()));
+        assert_eq!(format!("{balanced:?}"), "{1, 2, 3, 4, 5, 6, 7}");
+    }
+
+    #[test]
+    fn test_level_order() {
+        let empty: BinarySearchTree<i32> = BinarySearchTree::new();
+        assert!(empty.level_order().is_empty());
+
+        let tree = prequel_memes_tree();
+        let levels = tree.level_order();
         assert_eq!(
-            *tree.ceil(&"but i was going to tasche station").unwrap(),
-            "general kenobi"
+            levels,
+            vec![
+                vec![&"hello there"],
+                vec![&"general kenobi", &"you are a bold one"],
+                vec![
+                    &"back away...I will deal with this jedi slime myself",
+                    &"kill him",
+                    &"your move",
+                ],
+                vec![&"you fool"],
+            ]
         );
+        assert_eq!(levels.len(), tree.height());
+
+        let tree = BinarySearchTree::from_sorted((1..=10).collect());
+        let levels = tree.level_order();
         assert_eq!(
-            *tree.ceil(&"you underestimate my power").unwrap(),
-            "your move"
+            levels,
+            vec![
+                vec![&6],
+                vec![&3, &9],
+                vec![&2, &5, &8, &10],
+                vec![&1, &4, &7],
+            ]
         );
-        assert!(tree.ceil(&"your new empire").is_none());
+        assert_eq!(levels.len(), tree.height());
+        assert_eq!(levels.iter().map(Vec::len).sum::<usize>(), tree.len());
     }
 
     #[test]
-    fn test_iterator() {
+    fn test_to_pretty_string() {
+        let empty: BinarySearchTree<i32> = BinarySearchTree::new();
+        assert_eq!(empty.to_pretty_string(), "");
+
+        let mut tree = BinarySearchTree::from_sorted((1..=5).collect());
+        assert_eq!(
+            tree.to_pretty_string(),
+            concat!(
+                "    5 (height 2, balance -1)\n",
+                "        4 (height 1, balance 0)\n",
+                "3 (height 3, balance 0)\n",
+                "    2 (height 2, balance -1)\n",
+                "        1 (height 1, balance 0)\n",
+            )
+        );
+
+        tree.insert(6);
+        tree.insert(7);
+        assert_eq!(
+            tree.to_pretty_string(),
+            concat!(
+                "            7 (height 1, balance 0)\n",
+                "        6 (height 2, balance 1)\n",
+                "    5 (height 3, balance 1)\n",
+                "        4 (height 1, balance 0)\n",
+                "3 (height 4, balance 1)\n",
+                "    2 (height 2, balance -1)\n",
+                "        1 (height 1, balance 0)\n",
+            )
+        );
+
+        let tree: BinarySearchTree<&str> = vec!["b", "a"].into_iter().collect();
+        assert_eq!(
+            tree.to_pretty_string(),
+            "\"b\" (height 2, balance -1)\n    \"a\" (height 1, balance 0)\n"
+        );
+    }
+
+    #[test]
+    fn test_sample() {
+        use crate::math::{Rng, PCG32};
+
+        let mut rng = PCG32::from_seed(0);
+        let tree: BinarySearchTree<i32> = (0..100).map(|value| value * 3).collect();
+        for k in [0, 1, 10, 99] {
+            let sample = tree.sample(k, &mut rng);
+            assert_eq!(sample.len(), k);
+            assert!(sample.iter().all(|value| tree.search(value)));
+            let mut distinct = sample.clone();
+            distinct.sort();
+            distinct.dedup();
+            assert_eq!(distinct.len(), k);
+        }
+        for k in [100, 101, 1000] {
+            assert!(tree.sample(k, &mut rng).into_iter().eq(tree.iter()));
+        }
+        assert!(BinarySearchTree::<i32>::new()
+            .sample(3, &mut rng)
+            .is_empty());
+
+        // every value should be drawn about trials * k / n = 900 times
+        let tree: BinarySearchTree<usize> = (0..10).collect();
+        let mut counts = [0; 10];
+        for _ in 0..3000 {
+            for &value in tree.sample(3, &mut rng) {
+                counts[value] += 1;
+            }
+        }
+        assert!(
+            counts.iter().all(|count| (800..1000).contains(count)),
+            "{counts:?}"
+        );
+    }
+
+    #[test]
+    fn test_to_sorted_vec() {
+        let empty: BinarySearchTree<i32> = BinarySearchTree::new();
+        assert!(empty.to_sorted_vec().is_empty());
+
         let tree = prequel_memes_tree();
-        let mut iter = tree.iter();
+        let values = tree.to_sorted_vec();
+        assert_eq!(values, tree.iter().cloned().collect::<Vec<_>>());
+        assert_eq!(values.capacity(), tree.len());
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let tree: BinarySearchTree<u32> = (0..500).map(|_| rng.gen_range(0..1000)).collect();
         assert_eq!(
-            iter.next().unwrap(),
-            &"back away...I will deal with this jedi slime myself"
+            tree.to_sorted_vec(),
+            tree.iter().cloned().collect::<Vec<_>>()
         );
-        assert_eq!(iter.next().unwrap(), &"general kenobi");
-        assert_eq!(iter.next().unwrap(), &"hello there");
-        assert_eq!(iter.next().unwrap(), &"kill him");
-        assert_eq!(iter.next().unwrap(), &"you are a bold one");
-        assert_eq!(iter.next().unwrap(), &"you fool");
-        assert_eq!(iter.next().unwrap(), &"your move");
-        assert_eq!(iter.next(), None);
-        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_persistent_versions() {
+        let mut versions = vec![BinarySearchTree::new()];
+        for value in [5, 3, 8, 1, 4, 7, 9] {
+            let next = versions.last().unwrap().insert_persistent(value);
+            versions.push(next);
+        }
+        for value in [3, 9, 6, 5] {
+            let next = versions.last().unwrap().remove_persistent(&value);
+            versions.push(next);
+        }
+
+        let expected: [&[i32]; 12] = [
+            &[],
+            &[5],
+            &[3, 5],
+            &[3, 5, 8],
+            &[1, 3, 5, 8],
+            &[1, 3, 4, 5, 8],
+            &[1, 3, 4, 5, 7, 8],
+            &[1, 3, 4, 5, 7, 8, 9],
+            &[1, 4, 5, 7, 8, 9],
+            &[1, 4, 5, 7, 8],
+            // 6 was never inserted
+            &[1, 4, 5, 7, 8],
+            &[1, 4, 7, 8],
+        ];
+        assert_eq!(versions.len(), expected.len());
+        for (tree, expected) in versions.iter().zip(expected) {
+            assert!(tree.is_valid());
+            assert_eq!(tree.len(), expected.len());
+            assert!(tree.iter().eq(expected.iter()));
+        }
+    }
+
+    /// Counts the nodes of new which are not shared with old, comparing the
+    /// children at the same position in both trees
+    fn unshared_nodes(old: Option<&BinarySearchTree<i32>>, new: &BinarySearchTree<i32>) -> usize {
+        let children = [
+            (old.and_then(|old| old.left.as_ref()), new.left.as_ref()),
+            (old.and_then(|old| old.right.as_ref()), new.right.as_ref()),
+        ];
+        // the root is stored inline, so it is never shared
+        1 + children
+            .into_iter()
+            .map(|children| match children {
+                (Some(old), Some(new)) if Arc::ptr_eq(old, new) => 0,
+                (old, Some(new)) => unshared_nodes(old.map(|old| &**old), new),
+                (_, None) => 0,
+            })
+            .sum::<usize>()
+    }
+
+    #[test]
+    fn test_persistent_versions_share_nodes() {
+        let mut rng = StdRng::seed_from_u64(0);
+        // 1023 values give a perfectly balanced tree of height 10
+        let mut versions = vec![BinarySearchTree::from_sorted(
+            (0..2046).step_by(2).collect(),
+        )];
+        for _ in 0..100 {
+            let current = versions.last().unwrap();
+            let value = rng.gen_range(0..2046);
+            let next = if rng.gen_bool(0.5) {
+                current.insert_persistent(value)
+            } else {
+                current.remove_persistent(&value)
+            };
+            assert!(next.is_valid());
+            // only the search paths were copied
+            assert!(unshared_nodes(Some(current), &next) <= 2 * current.height() + 1);
+            versions.push(next);
+        }
+
+        // inserting a present value or removing an absent one copies only the root
+        let current = versions.last().unwrap();
+        let present = *current.minimum().unwrap();
+        assert_eq!(
+            unshared_nodes(Some(current), &current.insert_persistent(present)),
+            1
+        );
+        assert_eq!(
+            unshared_nodes(Some(current), &current.remove_persistent(&1)),
+            1
+        );
+
+        // a tree built from the same values shares nothing
+        let copy: BinarySearchTree<i32> = current.iter().copied().collect();
+        assert_eq!(unshared_nodes(Some(current), &copy), copy.len());
+    }
+
+    #[test]
+    fn test_insert_present_keeps_nodes_shared() {
+        let tree = BinarySearchTree::from_sorted((0..200).step_by(2).collect());
+        let mut copy = tree.clone();
+        for value in [0, 2, 76, 150, 198] {
+            assert!(!copy.insert(value));
+            assert_eq!(*copy.get_or_insert(value), value);
+            // every node but the inline root is still shared with the original
+            assert_eq!(unshared_nodes(Some(&tree), &copy), 1);
+        }
+        assert!(copy.is_valid());
+        assert_eq!(copy.len(), 100);
+    }
+
+    #[test]
+    fn test_remove_absent_keeps_nodes_shared() {
+        let tree = BinarySearchTree::from_sorted((0..200).step_by(2).collect());
+        let mut copy = tree.clone();
+        for value in [-1, 1, 77, 151, 199, 500] {
+            assert_eq!(copy.remove(&value), None);
+            // every node but the inline root is still shared with the original
+            assert_eq!(unshared_nodes(Some(&tree), &copy), 1);
+        }
+        assert!(copy.is_valid());
+        assert_eq!(copy.len(), 100);
+    }
+
+    #[test]
+    fn test_is_valid_random_operations() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut tree = BinarySearchTree::new();
+        let mut expected = std::collections::BTreeSet::new();
+        assert!(tree.is_valid());
+        for _ in 0..2000 {
+            let value = rng.gen_range(0..200);
+            if rng.gen_bool(0.6) {
+                assert_eq!(tree.insert(value), expected.insert(value));
+            } else {
+                let removed = tree.remove(&value);
+                assert_eq!(removed.is_some(), expected.remove(&value));
+                assert!(removed.is_none_or(|removed| removed == value));
+            }
+            assert!(tree.is_valid());
+            assert_eq!(tree.len(), expected.len());
+        }
+        assert!(tree.iter().eq(expected.iter()));
+    }
+
+    #[test]
+    fn test_is_valid_detects_corruption() {
+        let mut tree: BinarySearchTree<i32> = vec![2, 1, 3].into_iter().collect();
+        assert!(tree.is_valid());
+        Arc::make_mut(tree.left.as_mut().unwrap()).value = Some(5);
+        assert!(!tree.is_valid());
+
+        let mut tree: BinarySearchTree<i32> = vec![2, 1, 3].into_iter().collect();
+        tree.size = 4;
+        assert!(!tree.is_valid());
+    }
+
+    #[test]
+    fn test_is_balanced() {
+        assert!(BinarySearchTree::<i32>::new().is_balanced());
+        assert!(BinarySearchTree::from_sorted((0..100).collect()).is_balanced());
+        let tree: BinarySearchTree<i32> = vec![2, 1, 3, 4].into_iter().collect();
+        assert!(tree.is_balanced());
+        let tree: BinarySearchTree<i32> = (0..3).collect();
+        assert!(tree.is_valid());
+        assert!(!tree.is_balanced());
+    }
+
+    #[derive(Debug, Clone)]
+    struct Keyed {
+        key: i32,
+        payload: &'static str,
+    }
+
+    impl PartialEq for Keyed {
+        fn eq(&self, other: &Self) -> bool {
+            self.key == other.key
+        }
+    }
+
+    impl Eq for Keyed {}
+
+    impl PartialOrd for Keyed {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Keyed {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.key.cmp(&other.key)
+        }
+    }
+
+    #[test]
+    fn test_get_or_insert() {
+        let mut tree = BinarySearchTree::new();
+        for (key, payload) in [(5, "five"), (2, "two"), (8, "eight")] {
+            tree.insert(Keyed { key, payload });
+        }
+        let stored = tree.get_or_insert(Keyed {
+            key: 2,
+            payload: "deux",
+        });
+        assert_eq!(stored.payload, "two");
+        let stored = tree.get_or_insert(Keyed {
+            key: 3,
+            payload: "three",
+        });
+        assert_eq!(stored.payload, "three");
+        assert_eq!(tree.len(), 4);
+        assert!(tree.is_valid());
+
+        let mut empty = BinarySearchTree::new();
+        assert_eq!(*empty.get_or_insert(1), 1);
+        assert_eq!(*empty.get_or_insert(1), 1);
+        assert_eq!(empty.len(), 1);
+    }
+
+    #[test]
+    fn test_pop_min_and_pop_max() {
+        let values = [50, 30, 70, 20, 40, 60, 80, 35, 45, 65];
+        let mut tree: BinarySearchTree<i32> = values.iter().copied().collect();
+        let mut sorted = values.to_vec();
+        sorted.sort();
+        let mut drained = Vec::new();
+        while let Some(value) = tree.pop_min() {
+            drained.push(value);
+            assert!(tree.is_valid());
+        }
+        assert_eq!(drained, sorted);
+        assert!(tree.is_empty());
+        assert_eq!(tree.pop_min(), None);
+
+        let mut tree: BinarySearchTree<i32> = values.iter().copied().collect();
+        let mut drained = Vec::new();
+        while let Some(value) = tree.pop_max() {
+            drained.push(value);
+            assert!(tree.is_valid());
+        }
+        sorted.reverse();
+        assert_eq!(drained, sorted);
+        assert_eq!(tree.pop_max(), None);
+
+        // the tree is still usable afterwards
+        tree.insert(1);
+        assert_eq!(tree.pop_max(), Some(1));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = || (1..=10).collect::<BinarySearchTree<i32>>();
+        let b = || (6..=15).collect::<BinarySearchTree<i32>>();
+        let c = || (20..=25).collect::<BinarySearchTree<i32>>();
+        let empty = BinarySearchTree::<i32>::new;
+
+        // partial overlap
+        let union = a().union(b());
+        assert!(union.is_valid() && union.is_balanced());
+        assert!(union.iter().copied().eq(1..=15));
+        let intersection = a().intersection(b());
+        assert!(intersection.is_valid() && intersection.is_balanced());
+        assert!(intersection.iter().copied().eq(6..=10));
+        let difference = a().difference(b());
+        assert!(difference.is_valid() && difference.is_balanced());
+        assert!(difference.iter().copied().eq(1..=5));
+
+        // disjoint
+        let union = a().union(c());
+        assert!(union.is_valid());
+        assert!(union.iter().copied().eq((1..=10).chain(20..=25)));
+        assert!(a().intersection(c()).is_empty());
+        assert!(a().difference(c()).iter().copied().eq(1..=10));
+
+        // one tree empty
+        assert!(a().union(empty()).iter().copied().eq(1..=10));
+        assert!(empty().union(a()).iter().copied().eq(1..=10));
+        assert!(a().intersection(empty()).is_empty());
+        assert!(empty().difference(a()).is_empty());
+        assert!(a().difference(empty()).iter().copied().eq(1..=10));
+    }
+
+    thread_local! {
+        static COMPARISONS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    }
+
+    /// An integer which counts how often it is compared
+    #[derive(PartialEq, Eq, Debug, Clone)]
+    struct Counted(i32);
+
+    impl PartialOrd for Counted {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Counted {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            COMPARISONS.with(|count| count.set(count.get() + 1));
+            self.0.cmp(&other.0)
+        }
+    }
+
+    #[test]
+    fn test_remove_compares_once_per_node() {
+        // 8 is the root, 4 and 12 are its children
+        let mut tree = BinarySearchTree::from_sorted((1..=15).map(Counted).collect());
+        COMPARISONS.with(|count| count.set(0));
+        // the successor 9 is found without any further comparison
+        tree.remove(&Counted(8));
+        assert_eq!(COMPARISONS.with(|count| count.get()), 1);
+        assert_eq!(tree.value, Some(Counted(9)));
+
+        COMPARISONS.with(|count| count.set(0));
+        tree.remove(&Counted(4));
+        assert_eq!(COMPARISONS.with(|count| count.get()), 2);
+
+        COMPARISONS.with(|count| count.set(0));
+        tree.remove(&Counted(15));
+        assert_eq!(COMPARISONS.with(|count| count.get()), 4);
+
+        assert!(tree.is_valid());
+        assert!(tree
+            .iter()
+            .map(|value| value.0)
+            .eq([1, 2, 3, 5, 6, 7, 9, 10, 11, 12, 13, 14]));
+    }
+
+    #[test]
+    fn test_entry_compares_once_per_node() {
+        let mut tree = BinarySearchTree::from_sorted((1..=15).map(Counted).collect());
+        // 16 is compared against 8, 12, 14 and 15, and then inserted without
+        // any further comparison
+        COMPARISONS.with(|count| count.set(0));
+        let entry = tree.entry(Counted(16));
+        assert!(!entry.is_occupied());
+        assert_eq!(COMPARISONS.with(|count| count.get()), 4);
+        assert_eq!(entry.or_insert(), &Counted(16));
+        assert_eq!(COMPARISONS.with(|count| count.get()), 4);
+        assert_eq!(tree.len(), 16);
+        assert_eq!(tree.height(), 5);
+
+        // 10 is found below 8 and 12, and the tree is left unchanged
+        COMPARISONS.with(|count| count.set(0));
+        let entry = tree.entry(Counted(10));
+        assert!(entry.is_occupied());
+        assert_eq!(entry.or_insert(), &Counted(10));
+        assert_eq!(COMPARISONS.with(|count| count.get()), 3);
+        assert_eq!(tree.len(), 16);
+
+        assert!(tree.is_valid());
+        assert!(tree.iter().map(|value| value.0).eq(1..=16));
+    }
+
+    #[test]
+    fn test_entry() {
+        use super::Entry;
+
+        let mut tree = BinarySearchTree::new();
+        let stored = tree
+            .entry(Keyed {
+                key: 5,
+                payload: "five",
+            })
+            .or_insert();
+        assert_eq!(stored.payload, "five");
+        for (key, payload) in [(2, "two"), (8, "eight"), (7, "seven")] {
+            tree.entry(Keyed { key, payload }).or_insert();
+        }
+        assert_eq!(tree.len(), 4);
+        assert!(tree.is_valid());
+
+        match tree.entry(Keyed {
+            key: 8,
+            payload: "huit",
+        }) {
+            Entry::Occupied(entry) => assert_eq!(entry.get().payload, "eight"),
+            Entry::Vacant(_) => panic!("8 is in the tree"),
+        }
+        match tree.entry(Keyed {
+            key: 6,
+            payload: "six",
+        }) {
+            Entry::Occupied(_) => panic!("6 is not in the tree"),
+            Entry::Vacant(entry) => {
+                assert_eq!(entry.get().payload, "six");
+                // the value is given back and the tree is left unchanged
+                assert_eq!(entry.into_value().payload, "six");
+            }
+        }
+        assert_eq!(tree.len(), 4);
+        assert!(tree.is_valid());
+        assert!(!tree.search(&Keyed {
+            key: 6,
+            payload: ""
+        }));
+
+        match tree.entry(Keyed {
+            key: 6,
+            payload: "six",
+        }) {
+            Entry::Occupied(_) => panic!("6 is not in the tree"),
+            Entry::Vacant(entry) => assert_eq!(entry.insert().payload, "six"),
+        }
+        assert_eq!(tree.len(), 5);
+        assert!(tree.is_valid());
+        assert!(tree.iter().map(|value| value.key).eq([2, 5, 6, 7, 8]));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        use serde::de::value::{Error, SeqDeserializer};
+        use serde::Deserialize;
+        use serde_test::{assert_tokens, Token};
+
+        let tree: BinarySearchTree<i32> = vec![5, 3, 8, 1, 4].into_iter().collect();
+        assert_tokens(
+            &tree,
+            &[
+                Token::Seq { len: Some(5) },
+                Token::I32(1),
+                Token::I32(3),
+                Token::I32(4),
+                Token::I32(5),
+                Token::I32(8),
+                Token::SeqEnd,
+            ],
+        );
+
+        // the serialized order does not matter and duplicates are dropped
+        let values = vec![8, 1, 5, 3, 4, 1];
+        let decoded = BinarySearchTree::<i32>::deserialize(SeqDeserializer::<_, Error>::new(
+            values.into_iter(),
+        ))
+        .unwrap();
+        assert!(decoded.is_valid() && decoded.is_balanced());
+        assert_eq!(decoded, tree);
+
+        let memes = prequel_memes_tree();
+        let tree: BinarySearchTree<String> = memes.iter().map(|&meme| meme.into()).collect();
+        let tokens: Vec<_> = std::iter::once(Token::Seq {
+            len: Some(memes.len()),
+        })
+        .chain(memes.iter().map(|&meme| Token::Str(meme)))
+        .chain(std::iter::once(Token::SeqEnd))
+        .collect();
+        assert_tokens(&tree, &tokens);
+
+        let empty: BinarySearchTree<String> = BinarySearchTree::new();
+        assert_tokens(&empty, &[Token::Seq { len: Some(0) }, Token::SeqEnd]);
     }
 }
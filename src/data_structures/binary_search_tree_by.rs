@@ -0,0 +1,273 @@
+use std::cmp::Ordering;
+
+struct Node<T> {
+    value: T,
+    left: Option<Box<Node<T>>>,
+    right: Option<Box<Node<T>>>,
+}
+
+/// A Binary Search Tree (BST) ordered by a comparator function instead of
+/// `Ord`. Two values which compare as `Ordering::Equal` are considered to be
+/// the same value, so only the first of them is stored.
+pub struct BinarySearchTreeBy<T, F>
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    root: Option<Box<Node<T>>>,
+    len: usize,
+    compare: F,
+}
+
+impl<T, F> BinarySearchTreeBy<T, F>
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    /// Create a new, empty BST ordered by compare
+    pub fn new(compare: F) -> BinarySearchTreeBy<T, F> {
+        BinarySearchTreeBy {
+            root: None,
+            len: 0,
+            compare,
+        }
+    }
+
+    /// Returns the number of values in this tree
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if this tree contains no values
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Find a value in this tree. Returns true if value is in this
+    /// tree, and false otherwise
+    pub fn search(&self, value: &T) -> bool {
+        let mut current = &self.root;
+        while let Some(node) = current {
+            current = match (self.compare)(&node.value, value) {
+                Ordering::Equal => return true,
+                Ordering::Greater => &node.left,
+                Ordering::Less => &node.right,
+            };
+        }
+        false
+    }
+
+    /// Insert a value into the appropriate location in this tree.
+    /// Returns true if the value was not already present.
+    pub fn insert(&mut self, value: T) -> bool {
+        let mut current = &mut self.root;
+        while let Some(node) = current {
+            current = match (self.compare)(&node.value, &value) {
+                Ordering::Equal => return false,
+                Ordering::Greater => &mut node.left,
+                Ordering::Less => &mut node.right,
+            };
+        }
+        *current = Some(Box::new(Node {
+            value,
+            left: None,
+            right: None,
+        }));
+        self.len += 1;
+        true
+    }
+
+    /// Remove a value from this tree. Returns true if it was present.
+    pub fn remove(&mut self, value: &T) -> bool {
+        let removed = remove(&mut self.root, value, &self.compare);
+        if removed {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    /// Returns the smallest value in this tree
+    pub fn minimum(&self) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(child) = &node.left {
+            node = child;
+        }
+        Some(&node.value)
+    }
+
+    /// Returns the largest value in this tree
+    pub fn maximum(&self) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(child) = &node.right {
+            node = child;
+        }
+        Some(&node.value)
+    }
+
+    /// Returns the largest value in this tree smaller than value
+    pub fn floor(&self, value: &T) -> Option<&T> {
+        let mut current = &self.root;
+        let mut floor = None;
+        while let Some(node) = current {
+            current = match (self.compare)(&node.value, value) {
+                Ordering::Equal => return Some(&node.value),
+                Ordering::Greater => &node.left,
+                Ordering::Less => {
+                    floor = Some(&node.value);
+                    &node.right
+                }
+            };
+        }
+        floor
+    }
+
+    /// Returns the smallest value in this tree larger than value
+    pub fn ceil(&self, value: &T) -> Option<&T> {
+        let mut current = &self.root;
+        let mut ceil = None;
+        while let Some(node) = current {
+            current = match (self.compare)(&node.value, value) {
+                Ordering::Equal => return Some(&node.value),
+                Ordering::Greater => {
+                    ceil = Some(&node.value);
+                    &node.left
+                }
+                Ordering::Less => &node.right,
+            };
+        }
+        ceil
+    }
+
+    /// Returns a new iterator which iterates over this tree in order
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let mut iter = Iter { stack: Vec::new() };
+        iter.stack_push_left(&self.root);
+        iter
+    }
+}
+
+/// Removes value from the subtree rooted at link, returning true if it was
+/// present
+fn remove<T, F>(link: &mut Option<Box<Node<T>>>, value: &T, compare: &F) -> bool
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    let node = match link {
+        Some(node) => node,
+        None => return false,
+    };
+    match compare(&node.value, value) {
+        Ordering::Greater => remove(&mut node.left, value, compare),
+        Ordering::Less => remove(&mut node.right, value, compare),
+        Ordering::Equal => {
+            *link = match (node.left.take(), node.right.take()) {
+                (None, None) => None,
+                (Some(child), None) | (None, Some(child)) => Some(child),
+                (Some(left), Some(right)) => {
+                    // Replace the value with its in-order successor
+                    let mut right = Some(right);
+                    node.value = take_minimum(&mut right);
+                    node.left = Some(left);
+                    node.right = right;
+                    link.take()
+                }
+            };
+            true
+        }
+    }
+}
+
+/// Detaches the smallest value from the non-empty subtree rooted at link
+fn take_minimum<T>(link: &mut Option<Box<Node<T>>>) -> T {
+    let node = link.as_mut().unwrap();
+    if node.left.is_some() {
+        return take_minimum(&mut node.left);
+    }
+    let node = link.take().unwrap();
+    *link = node.right;
+    node.value
+}
+
+struct Iter<'a, T> {
+    stack: Vec<&'a Node<T>>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    fn stack_push_left(&mut self, mut current: &'a Option<Box<Node<T>>>) {
+        while let Some(node) = current {
+            self.stack.push(node);
+            current = &node.left;
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.stack.pop()?;
+        self.stack_push_left(&node.right);
+        Some(&node.value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BinarySearchTreeBy;
+
+    #[test]
+    fn test_absolute_value_order() {
+        let mut tree = BinarySearchTreeBy::new(|a: &i32, b: &i32| a.abs().cmp(&b.abs()));
+        for value in [-5, 3, -1, 8, -7, 2, -9] {
+            assert!(tree.insert(value));
+        }
+        // -3 compares equal to 3
+        assert!(!tree.insert(-3));
+        assert_eq!(tree.len(), 7);
+        assert_eq!(tree.minimum(), Some(&-1));
+        assert_eq!(tree.maximum(), Some(&-9));
+        assert_eq!(
+            tree.iter().copied().collect::<Vec<_>>(),
+            vec![-1, 2, 3, -5, -7, 8, -9]
+        );
+        assert!(tree.search(&-3));
+        assert!(tree.search(&1));
+        assert!(!tree.search(&4));
+        assert_eq!(tree.floor(&-4), Some(&3));
+        assert_eq!(tree.ceil(&-4), Some(&-5));
+        assert_eq!(tree.floor(&0), None);
+        assert_eq!(tree.ceil(&10), None);
+
+        assert!(tree.remove(&5));
+        assert!(tree.remove(&1));
+        assert!(!tree.remove(&1));
+        assert_eq!(tree.len(), 5);
+        assert_eq!(tree.minimum(), Some(&2));
+        assert_eq!(
+            tree.iter().copied().collect::<Vec<_>>(),
+            vec![2, 3, -7, 8, -9]
+        );
+    }
+
+    #[test]
+    fn test_string_length_order() {
+        let mut tree = BinarySearchTreeBy::new(|a: &&str, b: &&str| b.len().cmp(&a.len()));
+        for word in ["a", "tree", "sorted", "by", "length", "descending"] {
+            tree.insert(word);
+        }
+        // "length" has the same length as "sorted"
+        assert_eq!(tree.len(), 5);
+        assert_eq!(tree.minimum(), Some(&"descending"));
+        assert_eq!(tree.maximum(), Some(&"a"));
+        assert_eq!(
+            tree.iter().copied().collect::<Vec<_>>(),
+            vec!["descending", "sorted", "tree", "by", "a"]
+        );
+        assert!(tree.remove(&"tree"));
+        assert!(tree.remove(&"descending"));
+        assert!(tree.remove(&"sorted"));
+        assert_eq!(tree.iter().copied().collect::<Vec<_>>(), vec!["by", "a"]);
+        assert!(tree.remove(&"by"));
+        assert!(tree.remove(&"a"));
+        assert!(tree.is_empty());
+        assert_eq!(tree.minimum(), None);
+    }
+}
@@ -0,0 +1,207 @@
+use crate::bit_manipulation::hamming_weight;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A fixed-size set of the integers `0..len`, stored as one bit per integer
+/// in a vector of u64 words. Bit i lives in word `i / 64` at position
+/// `i % 64`, and the unused high bits of the last word are always zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitSet {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitSet {
+    /// Creates a set which can hold the integers `0..nbits`, all of them
+    /// initially absent
+    pub fn new(nbits: usize) -> Self {
+        BitSet {
+            words: vec![0; nbits.div_ceil(WORD_BITS)],
+            len: nbits,
+        }
+    }
+
+    /// Returns the number of bits, i.e. one more than the largest integer
+    /// the set can hold
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Adds i to the set
+    pub fn set(&mut self, i: usize) {
+        let (word, mask) = self.position(i);
+        self.words[word] |= mask;
+    }
+
+    /// Removes i from the set
+    pub fn clear(&mut self, i: usize) {
+        let (word, mask) = self.position(i);
+        self.words[word] &= !mask;
+    }
+
+    /// Returns true if i is in the set
+    pub fn get(&self, i: usize) -> bool {
+        let (word, mask) = self.position(i);
+        self.words[word] & mask != 0
+    }
+
+    /// Returns the number of integers in the set, counting the bits of
+    /// every word with Kernighan's algorithm
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|&word| hamming_weight(word) as usize)
+            .sum()
+    }
+
+    /// Returns the integers present in either set
+    pub fn union(&self, other: &BitSet) -> BitSet {
+        self.combine(other, |a, b| a | b)
+    }
+
+    /// Returns the integers present in both sets
+    pub fn intersection(&self, other: &BitSet) -> BitSet {
+        self.combine(other, |a, b| a & b)
+    }
+
+    /// Returns the integers present in this set but not in other
+    pub fn difference(&self, other: &BitSet) -> BitSet {
+        self.combine(other, |a, b| a & !b)
+    }
+
+    /// Returns the word index and the mask of bit i
+    fn position(&self, i: usize) -> (usize, u64) {
+        if i >= self.len {
+            panic!("Bit index out of bounds");
+        }
+        (i / WORD_BITS, 1 << (i % WORD_BITS))
+    }
+
+    /// Combines the sets word by word. op must map two zero bits to zero, so
+    /// that the unused bits of the last word stay zero.
+    fn combine(&self, other: &BitSet, op: impl Fn(u64, u64) -> u64) -> BitSet {
+        if self.len != other.len {
+            panic!("Bit sets must have the same number of bits");
+        }
+        BitSet {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(&a, &b)| op(a, b))
+                .collect(),
+            len: self.len,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use std::collections::BTreeSet;
+
+    fn random_set(rng: &mut StdRng, nbits: usize) -> BitSet {
+        let mut set = BitSet::new(nbits);
+        for i in 0..nbits {
+            if rng.gen_bool(0.3) {
+                set.set(i);
+            }
+        }
+        set
+    }
+
+    fn members(set: &BitSet) -> BTreeSet<usize> {
+        (0..set.len()).filter(|&i| set.get(i)).collect()
+    }
+
+    #[test]
+    fn test_word_boundaries() {
+        let mut set = BitSet::new(130);
+        assert_eq!(set.len(), 130);
+        assert_eq!(set.count_ones(), 0);
+        let boundaries = [0, 1, 62, 63, 64, 65, 127, 128, 129];
+        for &i in &boundaries {
+            set.set(i);
+        }
+        assert_eq!(set.count_ones(), boundaries.len());
+        for i in 0..130 {
+            assert_eq!(set.get(i), boundaries.contains(&i), "i = {i}");
+        }
+
+        // setting twice and clearing absent bits change nothing
+        set.set(64);
+        set.clear(100);
+        assert_eq!(set.count_ones(), boundaries.len());
+
+        set.clear(63);
+        set.clear(64);
+        set.clear(129);
+        assert!(!set.get(63) && !set.get(64) && !set.get(129));
+        assert!(set.get(62) && set.get(65) && set.get(128));
+        assert_eq!(set.count_ones(), boundaries.len() - 3);
+    }
+
+    #[test]
+    fn test_exact_multiple_of_word_size() {
+        let mut set = BitSet::new(128);
+        for i in 0..128 {
+            set.set(i);
+        }
+        assert_eq!(set.count_ones(), 128);
+        assert_eq!(set.difference(&set).count_ones(), 0);
+
+        let empty = BitSet::new(0);
+        assert!(empty.is_empty());
+        assert_eq!(empty.count_ones(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Bit index out of bounds")]
+    fn test_out_of_bounds() {
+        let mut set = BitSet::new(64);
+        set.set(64);
+    }
+
+    #[test]
+    #[should_panic(expected = "Bit sets must have the same number of bits")]
+    fn test_mismatched_lengths() {
+        BitSet::new(64).union(&BitSet::new(65));
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for nbits in [1, 63, 64, 65, 200] {
+            let a = random_set(&mut rng, nbits);
+            let b = random_set(&mut rng, nbits);
+            let c = random_set(&mut rng, nbits);
+            let empty = BitSet::new(nbits);
+
+            let (set_a, set_b) = (members(&a), members(&b));
+            assert_eq!(members(&a.union(&b)), &set_a | &set_b);
+            assert_eq!(members(&a.intersection(&b)), &set_a & &set_b);
+            assert_eq!(members(&a.difference(&b)), &set_a - &set_b);
+
+            assert_eq!(a.union(&b), b.union(&a));
+            assert_eq!(a.intersection(&b), b.intersection(&a));
+            assert_eq!(a.union(&empty), a);
+            assert_eq!(a.intersection(&empty), empty);
+            assert_eq!(a.union(&a), a);
+            assert_eq!(a.intersection(&a.union(&b)), a);
+            assert_eq!(a.difference(&b).intersection(&b), empty);
+            assert_eq!(
+                a.difference(&b.union(&c)),
+                a.difference(&b).intersection(&a.difference(&c))
+            );
+            assert_eq!(
+                a.union(&b).count_ones(),
+                a.count_ones() + b.count_ones() - a.intersection(&b).count_ones()
+            );
+        }
+    }
+}
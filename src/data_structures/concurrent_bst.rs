@@ -0,0 +1,174 @@
+use super::BinarySearchTree;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// A `BinarySearchTree` which can be shared between threads, e.g. by
+/// reference from scoped threads or inside an `Arc`.
+///
+/// Any number of readers may use the tree at once, while writers take turns
+/// and exclude all readers. Every method is atomic, and a guard returned by
+/// `read` sees the same tree for as long as it is held, so iterating through
+/// it gives a consistent view. A reader holding on to the guard blocks
+/// writers until it is done, so a long-running reader should rather take a
+/// snapshot with `read().clone()`, which is O(1) as the clone shares its
+/// nodes with the tree.
+///
+/// A thread which panics while writing poisons the lock, after which every
+/// method panics.
+pub struct ConcurrentBst<T>
+where
+    T: Ord,
+{
+    tree: RwLock<BinarySearchTree<T>>,
+}
+
+impl<T> Default for ConcurrentBst<T>
+where
+    T: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<BinarySearchTree<T>> for ConcurrentBst<T>
+where
+    T: Ord,
+{
+    fn from(tree: BinarySearchTree<T>) -> Self {
+        ConcurrentBst {
+            tree: RwLock::new(tree),
+        }
+    }
+}
+
+impl<T> ConcurrentBst<T>
+where
+    T: Ord,
+{
+    /// Create a new, empty tree
+    pub fn new() -> ConcurrentBst<T> {
+        BinarySearchTree::new().into()
+    }
+
+    /// Locks the tree for reading, e.g. to iterate over it
+    pub fn read(&self) -> RwLockReadGuard<'_, BinarySearchTree<T>> {
+        self.tree.read().unwrap()
+    }
+
+    /// Locks the tree for writing, e.g. to apply several changes atomically
+    pub fn write(&self) -> RwLockWriteGuard<'_, BinarySearchTree<T>> {
+        self.tree.write().unwrap()
+    }
+
+    /// Returns the number of values in the tree
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// Returns true if the tree contains no values
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// Returns true if value is in the tree
+    pub fn search(&self, value: &T) -> bool {
+        self.read().search(value)
+    }
+
+    /// Insert a value into the tree, if it is not already present. Returns
+    /// true if the value was inserted.
+    pub fn insert(&self, value: T) -> bool
+    where
+        T: Clone,
+    {
+        self.write().insert(value)
+    }
+
+    /// Remove a value from the tree, if it is present, and return it
+    pub fn remove(&self, value: &T) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.write().remove(value)
+    }
+
+    /// Returns the values of the tree in order, copied under a single lock
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.read().iter().cloned().collect()
+    }
+
+    /// Unwraps the tree
+    pub fn into_inner(self) -> BinarySearchTree<T> {
+        self.tree.into_inner().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ConcurrentBst;
+    use std::thread;
+
+    #[test]
+    fn single_thread() {
+        let tree = ConcurrentBst::new();
+        assert!(tree.is_empty());
+        assert!(tree.insert(5));
+        assert!(tree.insert(3));
+        assert!(tree.insert(8));
+        assert!(!tree.insert(3));
+        assert_eq!(tree.len(), 3);
+        assert!(tree.search(&3));
+        assert_eq!(tree.remove(&3), Some(3));
+        assert_eq!(tree.remove(&3), None);
+        assert!(!tree.search(&3));
+        assert_eq!(tree.to_vec(), vec![5, 8]);
+        assert_eq!(tree.read().iter().sum::<i32>(), 13);
+        assert!(tree.into_inner().is_valid());
+    }
+
+    #[test]
+    fn readers_and_writers() {
+        const WRITERS: usize = 4;
+        const VALUES_PER_WRITER: usize = 500;
+        let tree = ConcurrentBst::new();
+
+        thread::scope(|scope| {
+            for writer in 0..WRITERS {
+                let tree = &tree;
+                scope.spawn(move || {
+                    // Each writer owns the values congruent to writer modulo
+                    // WRITERS, and removes every odd one after inserting it
+                    for i in 0..VALUES_PER_WRITER {
+                        let value = i * WRITERS + writer;
+                        assert!(tree.insert(value));
+                        if i % 2 == 1 {
+                            assert_eq!(tree.remove(&value), Some(value));
+                        }
+                    }
+                });
+            }
+            for _ in 0..4 {
+                let tree = &tree;
+                scope.spawn(move || {
+                    for i in 0..200 {
+                        tree.search(&i);
+                        let tree = tree.read();
+                        assert!(tree.is_valid());
+                        assert_eq!(tree.iter().count(), tree.len());
+                    }
+                });
+            }
+        });
+
+        let tree = tree.into_inner();
+        assert!(tree.is_valid());
+        assert_eq!(tree.len(), WRITERS * VALUES_PER_WRITER / 2);
+        let expected: Vec<usize> = (0..WRITERS * VALUES_PER_WRITER)
+            .filter(|value| (value / WRITERS).is_multiple_of(2))
+            .collect();
+        assert_eq!(tree.iter().copied().collect::<Vec<_>>(), expected);
+    }
+}
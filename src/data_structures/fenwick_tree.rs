@@ -1,4 +1,4 @@
-use std::ops::{Add, AddAssign};
+use std::ops::{Add, AddAssign, Sub};
 
 /// Fenwick Tree / Binary Indexed Tree
 ///
@@ -40,6 +40,18 @@ impl<T: Add<Output = T> + AddAssign + Copy + Default> FenwickTree<T> {
     }
 }
 
+impl<T: Add<Output = T> + AddAssign + Sub<Output = T> + Copy + Default> FenwickTree<T> {
+    /// get the sum of [l, r]
+    pub fn range_sum(&self, l: usize, r: usize) -> T {
+        assert!(l <= r);
+        if l == 0 {
+            self.prefix_sum(r)
+        } else {
+            self.prefix_sum(r) - self.prefix_sum(l - 1)
+        }
+    }
+}
+
 /// get the lowest bit of `i`
 const fn lowbit(x: usize) -> usize {
     let x = x as isize;
@@ -49,6 +61,8 @@ const fn lowbit(x: usize) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
     #[test]
     fn it_works() {
         let mut ft = FenwickTree::with_len(10);
@@ -73,4 +87,24 @@ mod tests {
         assert_eq!(ft.prefix_sum(8), 45);
         assert_eq!(ft.prefix_sum(9), 55);
     }
+
+    #[test]
+    fn random_against_naive_sums() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let len = 100;
+        let mut ft = FenwickTree::with_len(len);
+        let mut arr = vec![0i64; len];
+        for _ in 0..1000 {
+            let i = rng.gen_range(0..len);
+            let delta = rng.gen_range(-100..100);
+            ft.add(i, delta);
+            arr[i] += delta;
+
+            let l = rng.gen_range(0..len);
+            let r = rng.gen_range(l..len);
+            assert_eq!(ft.range_sum(l, r), arr[l..=r].iter().sum());
+            assert_eq!(ft.prefix_sum(r), arr[..=r].iter().sum());
+        }
+        assert_eq!(ft.range_sum(0, len - 1), arr.iter().sum());
+    }
 }
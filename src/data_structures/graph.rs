@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt;
 
 #[derive(Debug, Clone)]
@@ -112,6 +112,49 @@ pub trait Graph {
         }
         edges
     }
+
+    /// Returns the nodes reachable from start in breadth-first order, so
+    /// nodes closer to start come first. Neighbours are visited in the order
+    /// their edges were added.
+    fn bfs(&self, start: &str) -> Result<Vec<&String>, NodeNotInGraph> {
+        let table = self.adjacency_table();
+        let (start, _) = table.get_key_value(start).ok_or(NodeNotInGraph)?;
+        let mut visited = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+        let mut order = Vec::new();
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for (next, _) in &table[node] {
+                if visited.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        Ok(order)
+    }
+
+    /// Returns the nodes reachable from start in depth-first preorder.
+    /// Neighbours are visited in the order their edges were added.
+    fn dfs(&self, start: &str) -> Result<Vec<&String>, NodeNotInGraph> {
+        let table = self.adjacency_table();
+        let (start, _) = table.get_key_value(start).ok_or(NodeNotInGraph)?;
+        let mut visited = HashSet::new();
+        let mut stack = vec![start];
+        let mut order = Vec::new();
+        while let Some(node) = stack.pop() {
+            if !visited.insert(node) {
+                continue;
+            }
+            order.push(node);
+            // Pushed in reverse, so that the first neighbour is explored first
+            for (next, _) in table[node].iter().rev() {
+                if !visited.contains(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        Ok(order)
+    }
 }
 
 #[cfg(test)]
@@ -152,6 +195,26 @@ mod test_undirected_graph {
             &vec![(String::from("b"), 5), (String::from("c"), 7)]
         );
     }
+
+    #[test]
+    fn test_traversals() {
+        let mut graph = UndirectedGraph::new();
+        graph.add_edge(("a", "b", 1));
+        graph.add_edge(("a", "c", 1));
+        graph.add_edge(("b", "d", 1));
+        graph.add_edge(("c", "d", 1));
+        graph.add_edge(("d", "e", 1));
+        // A second component, not reachable from the first
+        graph.add_edge(("x", "y", 1));
+
+        assert_eq!(graph.bfs("a").unwrap(), ["a", "b", "c", "d", "e"]);
+        assert_eq!(graph.dfs("a").unwrap(), ["a", "b", "d", "c", "e"]);
+        assert_eq!(graph.bfs("e").unwrap(), ["e", "d", "b", "c", "a"]);
+        assert_eq!(graph.bfs("y").unwrap(), ["y", "x"]);
+        assert_eq!(graph.dfs("x").unwrap(), ["x", "y"]);
+        assert!(graph.bfs("z").is_err());
+        assert!(graph.dfs("z").is_err());
+    }
 }
 
 #[cfg(test)]
@@ -217,4 +280,24 @@ mod test_directed_graph {
         assert!(graph.contains("c"));
         assert!(!graph.contains("d"));
     }
+
+    #[test]
+    fn test_traversals() {
+        let mut graph = DirectedGraph::new();
+        graph.add_edge(("a", "b", 1));
+        graph.add_edge(("a", "c", 1));
+        graph.add_edge(("b", "d", 1));
+        graph.add_edge(("c", "b", 1));
+        graph.add_edge(("d", "a", 1));
+        // e can reach the others, but not the other way around
+        graph.add_edge(("e", "a", 1));
+        graph.add_node("f");
+
+        assert_eq!(graph.bfs("a").unwrap(), ["a", "b", "c", "d"]);
+        assert_eq!(graph.dfs("a").unwrap(), ["a", "b", "d", "c"]);
+        assert_eq!(graph.bfs("e").unwrap(), ["e", "a", "b", "c", "d"]);
+        assert_eq!(graph.dfs("c").unwrap(), ["c", "b", "d", "a"]);
+        assert_eq!(graph.bfs("f").unwrap(), ["f"]);
+        assert_eq!(graph.dfs("f").unwrap(), ["f"]);
+    }
 }
@@ -51,36 +51,54 @@ impl<T> Heap<T> {
         let next = Some(self.items.swap_remove(0));
 
         if !self.is_empty() {
-            // Heapify Down
-            let mut idx = 0;
-            while self.children_present(idx) {
-                let cdx = {
-                    if self.right_child_idx(idx) >= self.len() {
-                        self.left_child_idx(idx)
-                    } else {
-                        let ldx = self.left_child_idx(idx);
-                        let rdx = self.right_child_idx(idx);
-                        if (self.comparator)(&self.items[ldx], &self.items[rdx]) {
-                            ldx
-                        } else {
-                            rdx
-                        }
-                    }
-                };
-                if !(self.comparator)(&self.items[idx], &self.items[cdx]) {
-                    self.items.swap(idx, cdx);
-                }
-                idx = cdx;
-            }
+            self.heapify_down(0);
         }
 
         next
     }
 
+    /// Returns the item which would be popped next, without removing it
+    pub fn peek(&self) -> Option<&T> {
+        self.items.first()
+    }
+
+    /// Builds a heap out of a Vec in O(n), by heapifying down every item
+    /// which has children, starting from the last one
+    pub fn from_vec(items: Vec<T>, comparator: fn(&T, &T) -> bool) -> Self {
+        let mut heap = Self { items, comparator };
+        for idx in (0..heap.len() / 2).rev() {
+            heap.heapify_down(idx);
+        }
+        heap
+    }
+
     pub fn iter(&self) -> Iter<'_, T> {
         self.items.iter()
     }
 
+    fn heapify_down(&mut self, mut idx: usize) {
+        while self.children_present(idx) {
+            let cdx = {
+                if self.right_child_idx(idx) >= self.len() {
+                    self.left_child_idx(idx)
+                } else {
+                    let ldx = self.left_child_idx(idx);
+                    let rdx = self.right_child_idx(idx);
+                    if (self.comparator)(&self.items[ldx], &self.items[rdx]) {
+                        ldx
+                    } else {
+                        rdx
+                    }
+                }
+            };
+            if (self.comparator)(&self.items[idx], &self.items[cdx]) {
+                break;
+            }
+            self.items.swap(idx, cdx);
+            idx = cdx;
+        }
+    }
+
     fn parent_idx(&self, idx: usize) -> Option<usize> {
         if idx > 0 {
             Some((idx - 1) / 2)
@@ -115,11 +133,23 @@ where
     pub fn new_max() -> Heap<T> {
         Self::new(|a, b| a > b)
     }
+
+    /// Create a MinHeap out of a Vec in O(n)
+    pub fn from_vec_min(items: Vec<T>) -> Heap<T> {
+        Self::from_vec(items, |a, b| a < b)
+    }
+
+    /// Create a MaxHeap out of a Vec in O(n)
+    pub fn from_vec_max(items: Vec<T>) -> Heap<T> {
+        Self::from_vec(items, |a, b| a > b)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
     #[test]
     fn test_empty_heap() {
         let mut heap: Heap<i32> = Heap::new_max();
@@ -195,4 +225,60 @@ mod tests {
         assert_eq!(heap.pop(), Some(11));
         assert_eq!(heap.pop(), None);
     }
+
+    #[test]
+    fn test_peek() {
+        let mut heap = Heap::new_min();
+        assert_eq!(heap.peek(), None);
+        heap.add(4);
+        heap.add(2);
+        heap.add(9);
+        assert_eq!(heap.peek(), Some(&2));
+        assert_eq!(heap.len(), 3);
+        heap.pop();
+        assert_eq!(heap.peek(), Some(&4));
+    }
+
+    #[test]
+    fn test_random_sorted_extraction() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut values: Vec<i32> = (0..1000).map(|_| rng.gen_range(-500..500)).collect();
+        let mut min_heap = Heap::new_min();
+        let mut max_heap = Heap::new_max();
+        for &value in &values {
+            min_heap.add(value);
+            max_heap.add(value);
+        }
+        values.sort();
+        let popped: Vec<i32> = std::iter::from_fn(|| min_heap.pop()).collect();
+        assert_eq!(popped, values);
+        values.reverse();
+        let popped: Vec<i32> = std::iter::from_fn(|| max_heap.pop()).collect();
+        assert_eq!(popped, values);
+    }
+
+    #[test]
+    fn test_from_vec() {
+        let heap: Heap<i32> = Heap::from_vec_min(vec![]);
+        assert!(heap.is_empty());
+
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut values: Vec<i32> = (0..1000).map(|_| rng.gen_range(-500..500)).collect();
+        let mut min_heap = Heap::from_vec_min(values.clone());
+        let mut max_heap = Heap::from_vec_max(values.clone());
+        assert_eq!(min_heap.len(), values.len());
+        // Every item satisfies the heap property against its parent
+        for idx in 1..min_heap.items.len() {
+            assert!(min_heap.items[(idx - 1) / 2] <= min_heap.items[idx]);
+            assert!(max_heap.items[(idx - 1) / 2] >= max_heap.items[idx]);
+        }
+        values.sort();
+        assert_eq!(min_heap.peek(), values.first());
+        assert_eq!(max_heap.peek(), values.last());
+        let popped: Vec<i32> = std::iter::from_fn(|| min_heap.pop()).collect();
+        assert_eq!(popped, values);
+        values.reverse();
+        let popped: Vec<i32> = std::iter::from_fn(|| max_heap.pop()).collect();
+        assert_eq!(popped, values);
+    }
 }
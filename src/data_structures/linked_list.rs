@@ -187,6 +187,14 @@ impl<T> LinkedList<T> {
         Self::get_ith_node(self.head, index).map(|ptr| unsafe { &(*ptr.as_ptr()).val })
     }
 
+    /// Returns an iterator over the list, from head to tail
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        Iter {
+            next: self.head,
+            marker: PhantomData,
+        }
+    }
+
     fn get_ith_node(node: Option<NonNull<Node<T>>>, index: i32) -> Option<NonNull<Node<T>>> {
         match node {
             None => None,
@@ -198,6 +206,25 @@ impl<T> LinkedList<T> {
     }
 }
 
+struct Iter<'a, T> {
+    next: Option<NonNull<Node<T>>>,
+    marker: PhantomData<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        // Safety: the nodes are owned by a list which is borrowed for 'a, so
+        // they cannot be freed or modified while this iterator is alive
+        self.next.map(|node_ptr| unsafe {
+            let node = &*node_ptr.as_ptr();
+            self.next = node.next;
+            &node.val
+        })
+    }
+}
+
 impl<T> Drop for LinkedList<T> {
     fn drop(&mut self) {
         // Pop items until there are none left
@@ -499,4 +526,30 @@ mod tests {
         assert!(retrived_item.is_some());
         assert_eq!("B", *retrived_item.unwrap());
     }
+
+    #[test]
+    fn iter_follows_head_and_tail_operations() {
+        let mut list = LinkedList::<i32>::new();
+        assert_eq!(list.iter().next(), None);
+        list.insert_at_tail(2);
+        list.insert_at_head(1);
+        list.insert_at_tail(3);
+        list.insert_at_head(0);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+
+        assert_eq!(list.delete_tail(), Some(3));
+        assert_eq!(list.delete_head(), Some(0));
+        list.insert_at_tail(4);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 4]);
+
+        assert_eq!(list.delete_head(), Some(1));
+        assert_eq!(list.delete_tail(), Some(4));
+        assert_eq!(list.delete_tail(), Some(2));
+        assert_eq!(list.delete_head(), None);
+        assert_eq!(list.delete_tail(), None);
+        assert_eq!(list.iter().next(), None);
+
+        list.insert_at_head(5);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![5]);
+    }
 }
@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    // Neighbours in the recency list, as indices into `entries`
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+/// A cache holding at most `capacity` entries, which evicts the least recently
+/// used entry to make room for a new one.
+///
+/// Entries are stored in a Vec and linked into a doubly-linked recency list
+/// by index, from the most recently used (head) to the least recently used
+/// (tail). A HashMap from key to index gives O(1) `get` and `put`.
+pub struct LruCache<K, V> {
+    map: HashMap<K, usize>,
+    entries: Vec<Entry<K, V>>,
+    head: Option<usize>,
+    tail: Option<usize>,
+    capacity: usize,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    pub fn with_capacity(capacity: usize) -> Self {
+        if capacity == 0 {
+            panic!("Capacity must be greater than zero");
+        }
+        Self {
+            map: HashMap::with_capacity(capacity),
+            entries: Vec::with_capacity(capacity),
+            head: None,
+            tail: None,
+            capacity,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the value cached for key, marking it as the most recently used
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let idx = *self.map.get(key)?;
+        self.move_to_front(idx);
+        Some(&self.entries[idx].value)
+    }
+
+    /// Caches value under key, marking it as the most recently used. If the
+    /// key was already cached its value is replaced. Otherwise, if the cache
+    /// was full, the least recently used entry is evicted and returned.
+    pub fn put(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if let Some(&idx) = self.map.get(&key) {
+            self.entries[idx].value = value;
+            self.move_to_front(idx);
+            return None;
+        }
+
+        if self.entries.len() < self.capacity {
+            let idx = self.entries.len();
+            self.entries.push(Entry {
+                key: key.clone(),
+                value,
+                prev: None,
+                next: None,
+            });
+            self.map.insert(key, idx);
+            self.push_front(idx);
+            return None;
+        }
+
+        // Reuse the slot of the least recently used entry
+        let idx = self.tail.unwrap();
+        self.unlink(idx);
+        let entry = &mut self.entries[idx];
+        let old_key = std::mem::replace(&mut entry.key, key.clone());
+        let old_value = std::mem::replace(&mut entry.value, value);
+        self.map.remove(&old_key);
+        self.map.insert(key, idx);
+        self.push_front(idx);
+        Some((old_key, old_value))
+    }
+
+    fn move_to_front(&mut self, idx: usize) {
+        if self.head != Some(idx) {
+            self.unlink(idx);
+            self.push_front(idx);
+        }
+    }
+
+    fn unlink(&mut self, idx: usize) {
+        let (prev, next) = (self.entries[idx].prev, self.entries[idx].next);
+        match prev {
+            Some(prev) => self.entries[prev].next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.entries[next].prev = prev,
+            None => self.tail = prev,
+        }
+    }
+
+    fn push_front(&mut self, idx: usize) {
+        self.entries[idx].prev = None;
+        self.entries[idx].next = self.head;
+        match self.head {
+            Some(head) => self.entries[head].prev = Some(idx),
+            None => self.tail = Some(idx),
+        }
+        self.head = Some(idx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eviction_order() {
+        let mut cache = LruCache::with_capacity(3);
+        assert!(cache.is_empty());
+        assert_eq!(cache.put("a", 1), None);
+        assert_eq!(cache.put("b", 2), None);
+        assert_eq!(cache.put("c", 3), None);
+        assert_eq!(cache.len(), 3);
+
+        // "a" becomes the most recently used, so "b" is evicted first
+        assert_eq!(cache.get(&"a"), Some(&1));
+        assert_eq!(cache.put("d", 4), Some(("b", 2)));
+        assert_eq!(cache.get(&"b"), None);
+        assert_eq!(cache.put("e", 5), Some(("c", 3)));
+        assert_eq!(cache.get(&"d"), Some(&4));
+        assert_eq!(cache.put("f", 6), Some(("a", 1)));
+        assert_eq!(cache.put("g", 7), Some(("e", 5)));
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.get(&"d"), Some(&4));
+        assert_eq!(cache.get(&"f"), Some(&6));
+        assert_eq!(cache.get(&"g"), Some(&7));
+    }
+
+    #[test]
+    fn test_update_refreshes_recency() {
+        let mut cache = LruCache::with_capacity(2);
+        cache.put(1, "one");
+        cache.put(2, "two");
+        assert_eq!(cache.put(1, "uno"), None);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.put(3, "three"), Some((2, "two")));
+        assert_eq!(cache.get(&1), Some(&"uno"));
+        assert_eq!(cache.get(&3), Some(&"three"));
+    }
+
+    #[test]
+    fn test_capacity_one() {
+        let mut cache = LruCache::with_capacity(1);
+        assert_eq!(cache.put('x', 1), None);
+        assert_eq!(cache.put('x', 2), None);
+        assert_eq!(cache.get(&'x'), Some(&2));
+        assert_eq!(cache.put('y', 3), Some(('x', 2)));
+        assert_eq!(cache.get(&'x'), None);
+        assert_eq!(cache.get(&'y'), Some(&3));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Capacity must be greater than zero")]
+    fn test_zero_capacity() {
+        LruCache::<i32, i32>::with_capacity(0);
+    }
+}
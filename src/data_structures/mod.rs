@@ -1,6 +1,9 @@
 mod avl_tree;
 mod b_tree;
 mod binary_search_tree;
+mod binary_search_tree_by;
+mod bit_set;
+mod concurrent_bst;
 mod fenwick_tree;
 mod floyds_algorithm;
 mod graph;
@@ -9,10 +12,12 @@ mod heap;
 mod infix_to_postfix;
 mod lazy_segment_tree;
 mod linked_list;
+mod lru_cache;
 mod postfix_evaluation;
 mod probabilistic;
 mod queue;
 mod rb_tree;
+mod ring_buffer;
 mod segment_tree;
 mod segment_tree_recursive;
 mod stack_using_singly_linked_list;
@@ -23,21 +28,30 @@ mod veb_tree;
 
 pub use self::avl_tree::AVLTree;
 pub use self::b_tree::BTree;
-pub use self::binary_search_tree::BinarySearchTree;
+pub use self::binary_search_tree::{
+    dedup_sorted, BinarySearchTree, Entry, OccupiedEntry, TreeStats, VacantEntry,
+};
+pub use self::binary_search_tree_by::BinarySearchTreeBy;
+pub use self::bit_set::BitSet;
+pub use self::concurrent_bst::ConcurrentBst;
 pub use self::fenwick_tree::FenwickTree;
 pub use self::floyds_algorithm::{detect_cycle, has_cycle};
 pub use self::graph::DirectedGraph;
+pub use self::graph::Graph;
+pub use self::graph::NodeNotInGraph;
 pub use self::graph::UndirectedGraph;
 pub use self::hash_table::HashTable;
 pub use self::heap::Heap;
 pub use self::infix_to_postfix::infix_to_postfix;
 pub use self::lazy_segment_tree::LazySegmentTree;
 pub use self::linked_list::LinkedList;
+pub use self::lru_cache::LruCache;
 pub use self::postfix_evaluation::evaluate_postfix;
 pub use self::probabilistic::bloom_filter;
 pub use self::probabilistic::count_min_sketch;
 pub use self::queue::Queue;
 pub use self::rb_tree::RBTree;
+pub use self::ring_buffer::RingBuffer;
 pub use self::segment_tree::SegmentTree;
 pub use self::segment_tree_recursive::SegmentTree as SegmentTreeRecursive;
 pub use self::stack_using_singly_linked_list::Stack;
@@ -1,3 +1,4 @@
+use crate::ciphers::Hasher as DigestHasher;
 use std::collections::hash_map::{DefaultHasher, RandomState};
 use std::hash::{BuildHasher, Hash, Hasher};
 
@@ -119,18 +120,29 @@ impl MultiBinaryBloomFilter {
         estimated_count_of_items: usize,
         max_false_positive_probability: f64,
     ) -> Self {
-        // Check Wikipedia for these formulae
-        let optimal_filter_size = (-(estimated_count_of_items as f64)
-            * max_false_positive_probability.ln()
-            / (2.0_f64.ln().powi(2)))
-        .ceil() as usize;
-        let optimal_hash_count = ((optimal_filter_size as f64 / estimated_count_of_items as f64)
-            * 2.0_f64.ln())
-        .ceil() as usize;
+        let (optimal_filter_size, optimal_hash_count) =
+            optimal_dimensions(estimated_count_of_items, max_false_positive_probability);
         Self::with_dimensions(optimal_filter_size, optimal_hash_count)
     }
 }
 
+/// Returns the filter size (in bits) and the number of hash functions that
+/// keep the false positive probability under the given bound
+fn optimal_dimensions(
+    estimated_count_of_items: usize,
+    max_false_positive_probability: f64,
+) -> (usize, usize) {
+    // Check Wikipedia for these formulae
+    let optimal_filter_size = (-(estimated_count_of_items as f64)
+        * max_false_positive_probability.ln()
+        / (2.0_f64.ln().powi(2)))
+    .ceil() as usize;
+    let optimal_hash_count = ((optimal_filter_size as f64 / estimated_count_of_items as f64)
+        * 2.0_f64.ln())
+    .ceil() as usize;
+    (optimal_filter_size, optimal_hash_count)
+}
+
 impl<Item: Hash> BloomFilter<Item> for MultiBinaryBloomFilter {
     fn insert(&mut self, item: Item) {
         for builder in &self.hash_builders {
@@ -160,13 +172,83 @@ impl<Item: Hash> BloomFilter<Item> for MultiBinaryBloomFilter {
     }
 }
 
+/// Computing `hash_count` hashes for every item can get expensive, though.
+/// Double hashing gets away with computing a single digest: split it into two
+/// numbers `h1` and `h2`, and use `h1 + i * h2` as the `i`-th hash. This
+/// does not increase the asymptotic false positive rate (Kirsch & Mitzenmacher).
+///
+/// Here the digest comes from any of the hashers of the `ciphers` module, as
+/// long as it is at least 16 bytes long, and items are byte slices.
+pub struct DigestBloomFilter<const DIGEST_BYTES: usize, H: DigestHasher<DIGEST_BYTES>> {
+    filter_size: usize,
+    bytes: Vec<u8>,
+    hash_count: usize,
+    hasher: std::marker::PhantomData<H>,
+}
+
+impl<const DIGEST_BYTES: usize, H: DigestHasher<DIGEST_BYTES>> DigestBloomFilter<DIGEST_BYTES, H> {
+    pub fn with_dimensions(filter_size: usize, hash_count: usize) -> Self {
+        assert!(
+            DIGEST_BYTES >= 16,
+            "The digest must be at least 16 bytes long"
+        );
+        assert!(filter_size > 0, "The filter size must be greater than zero");
+        Self {
+            filter_size,
+            bytes: vec![0; filter_size.div_ceil(8)],
+            hash_count,
+            hasher: std::marker::PhantomData,
+        }
+    }
+
+    pub fn new(estimated_count_of_items: usize, max_false_positive_probability: f64) -> Self {
+        assert!(
+            estimated_count_of_items > 0,
+            "The estimated count of items must be greater than zero"
+        );
+        assert!(
+            max_false_positive_probability > 0.0 && max_false_positive_probability < 1.0,
+            "The false positive probability must be strictly between 0 and 1"
+        );
+        let (optimal_filter_size, optimal_hash_count) =
+            optimal_dimensions(estimated_count_of_items, max_false_positive_probability);
+        Self::with_dimensions(optimal_filter_size, optimal_hash_count)
+    }
+
+    pub fn insert(&mut self, item: &[u8]) {
+        for index in self.indices(item) {
+            self.bytes[index / 8] |= 1 << (index % 8);
+        }
+    }
+
+    pub fn contains(&self, item: &[u8]) -> bool {
+        self.indices(item)
+            .all(|index| self.bytes[index / 8] & (1 << (index % 8)) != 0)
+    }
+
+    /// Returns the bit index of every hash of item
+    fn indices(&self, item: &[u8]) -> impl Iterator<Item = usize> {
+        let mut hasher = H::new_default();
+        hasher.update(item);
+        let digest = hasher.get_hash();
+        let h1 = u64::from_le_bytes(digest[..8].try_into().unwrap());
+        let h2 = u64::from_le_bytes(digest[8..16].try_into().unwrap());
+        let filter_size = self.filter_size as u64;
+        (0..self.hash_count as u64)
+            .map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % filter_size) as usize)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::ciphers::SHA256;
     use crate::data_structures::probabilistic::bloom_filter::{
-        BasicBloomFilter, BloomFilter, MultiBinaryBloomFilter, SingleBinaryBloomFilter,
+        BasicBloomFilter, BloomFilter, DigestBloomFilter, MultiBinaryBloomFilter,
+        SingleBinaryBloomFilter,
     };
     use quickcheck::{Arbitrary, Gen};
     use quickcheck_macros::quickcheck;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
     use std::collections::HashSet;
 
     #[derive(Debug, Clone)]
@@ -265,4 +347,50 @@ mod tests {
         let fp_rate = false_positives as f64 / tests as f64;
         assert!(fp_rate < 1.0); // This isn't really a test, but so that you have the `fp_rate` variable to print out, or evaluate
     }
+
+    #[test]
+    fn a_digest_bloom_filter_has_a_bounded_false_positive_rate() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let to_insert: HashSet<[u8; 16]> = (0..1_000).map(|_| rng.gen()).collect();
+        let mut filter = DigestBloomFilter::<32, SHA256>::new(to_insert.len(), 0.01);
+        for item in &to_insert {
+            filter.insert(item);
+        }
+        for item in &to_insert {
+            assert!(filter.contains(item));
+        }
+
+        let mut tests = 0;
+        let mut false_positives = 0;
+        while tests < 10_000 {
+            let other: [u8; 16] = rng.gen();
+            if to_insert.contains(&other) {
+                continue;
+            }
+            tests += 1;
+            if filter.contains(&other) {
+                false_positives += 1;
+            }
+        }
+        let fp_rate = false_positives as f64 / tests as f64;
+        assert!(fp_rate < 0.02, "false positive rate {fp_rate} is too high");
+    }
+
+    #[test]
+    #[should_panic(expected = "The estimated count of items must be greater than zero")]
+    fn a_digest_bloom_filter_needs_items() {
+        DigestBloomFilter::<32, SHA256>::new(0, 0.01);
+    }
+
+    #[test]
+    #[should_panic(expected = "The false positive probability must be strictly between 0 and 1")]
+    fn a_digest_bloom_filter_needs_a_false_positive_rate() {
+        DigestBloomFilter::<32, SHA256>::new(1_000, 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "The false positive probability must be strictly between 0 and 1")]
+    fn a_digest_bloom_filter_needs_a_false_positive_rate_below_one() {
+        DigestBloomFilter::<32, SHA256>::new(1_000, 1.0);
+    }
 }
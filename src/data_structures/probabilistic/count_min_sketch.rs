@@ -1,3 +1,4 @@
+use crate::ciphers::Hasher as DigestHasher;
 use std::collections::hash_map::RandomState;
 use std::fmt::{Debug, Formatter};
 use std::hash::{BuildHasher, Hash};
@@ -151,13 +152,102 @@ impl<Item: Hash, const WIDTH: usize, const DEPTH: usize> CountMinSketch
     }
 }
 
+/// A CountMinSketch whose dimensions are chosen at runtime, and whose items
+/// are byte slices hashed by any of the hashers of the `ciphers` module.
+///
+/// As in `DigestBloomFilter`, a single digest of at least 16 bytes is split
+/// into two numbers `h1` and `h2`, and row `i` uses `h1 + i * h2` as its hash
+/// instead of running `DEPTH` different hash functions.
+///
+/// With `WIDTH = ceil(e / epsilon)` and `DEPTH = ceil(ln(1 / delta))`, the
+/// estimate of an item exceeds its true count by more than `epsilon * N`,
+/// where `N` is the total of all counts, with probability at most `delta`.
+pub struct DigestCountMinSketch<const DIGEST_BYTES: usize, H: DigestHasher<DIGEST_BYTES>> {
+    width: usize,
+    depth: usize,
+    /// The `depth` rows of `width` counts, one after the other
+    counts: Vec<u64>,
+    hasher: std::marker::PhantomData<H>,
+}
+
+impl<const DIGEST_BYTES: usize, H: DigestHasher<DIGEST_BYTES>>
+    DigestCountMinSketch<DIGEST_BYTES, H>
+{
+    pub fn with_dimensions(width: usize, depth: usize) -> Self {
+        assert!(
+            DIGEST_BYTES >= 16,
+            "The digest must be at least 16 bytes long"
+        );
+        assert!(
+            width > 0 && depth > 0,
+            "The width and depth must be greater than zero"
+        );
+        Self {
+            width,
+            depth,
+            counts: vec![0; width * depth],
+            hasher: std::marker::PhantomData,
+        }
+    }
+
+    /// Creates a sketch whose estimates exceed the true counts by at most
+    /// `epsilon` times the total count, except with probability `delta`
+    pub fn new(epsilon: f64, delta: f64) -> Self {
+        assert!(epsilon > 0.0, "The error epsilon must be greater than zero");
+        assert!(
+            delta > 0.0 && delta < 1.0,
+            "The failure probability delta must be strictly between 0 and 1"
+        );
+        let width = (std::f64::consts::E / epsilon).ceil() as usize;
+        let depth = (1.0 / delta).ln().ceil() as usize;
+        Self::with_dimensions(width, depth)
+    }
+
+    pub fn add(&mut self, item: &[u8]) {
+        self.add_count(item, 1)
+    }
+
+    /// Adds count occurrences of item; counts saturate at `u64::MAX`
+    /// instead of overflowing
+    pub fn add_count(&mut self, item: &[u8], count: u64) {
+        for index in self.indices(item) {
+            self.counts[index] = self.counts[index].saturating_add(count);
+        }
+    }
+
+    /// Returns the smallest count of item over all rows, which is never less
+    /// than the number of times it was added
+    pub fn estimate(&self, item: &[u8]) -> u64 {
+        self.indices(item)
+            .map(|index| self.counts[index])
+            .min()
+            .unwrap()
+    }
+
+    /// Returns the index in `counts` of item in every row
+    fn indices(&self, item: &[u8]) -> impl Iterator<Item = usize> {
+        let mut hasher = H::new_default();
+        hasher.update(item);
+        let digest = hasher.get_hash();
+        let h1 = u64::from_le_bytes(digest[..8].try_into().unwrap());
+        let h2 = u64::from_le_bytes(digest[8..16].try_into().unwrap());
+        let width = self.width;
+        (0..self.depth).map(move |row| {
+            let hash = h1.wrapping_add((row as u64).wrapping_mul(h2));
+            row * width + (hash % width as u64) as usize
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::ciphers::SHA256;
     use crate::data_structures::probabilistic::count_min_sketch::{
-        CountMinSketch, HashCountMinSketch,
+        CountMinSketch, DigestCountMinSketch, HashCountMinSketch,
     };
     use quickcheck::{Arbitrary, Gen};
-    use std::collections::HashSet;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use std::collections::{HashMap, HashSet};
 
     #[test]
     fn hash_functions_should_hash_differently() {
@@ -244,4 +334,86 @@ mod tests {
             assert!(exact_ratio > 0.7); // the proof is quite hard, but this should be OK
         }
     }
+
+    #[test]
+    fn digest_sketch_counts_a_single_item_exactly() {
+        let mut sketch = DigestCountMinSketch::<32, SHA256>::with_dimensions(10, 4);
+        assert_eq!(sketch.estimate(b"test"), 0);
+        sketch.add(b"test");
+        sketch.add(b"test");
+        sketch.add_count(b"test", 3);
+        assert_eq!(sketch.estimate(b"test"), 5);
+        // every row holds the count exactly once
+        assert_eq!(sketch.counts.iter().sum::<u64>(), 5 * 4);
+    }
+
+    #[test]
+    fn digest_sketch_estimates_are_bounded() {
+        let epsilon = 0.01;
+        let delta = 0.01;
+        let mut sketch = DigestCountMinSketch::<32, SHA256>::new(epsilon, delta);
+        assert_eq!((sketch.width, sketch.depth), (272, 5));
+
+        // a skewed stream: item i occurs about 1000 / (i + 1) times
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut exact: HashMap<u32, u64> = HashMap::new();
+        let mut total = 0;
+        for _ in 0..20_000 {
+            let item = (1000.0 / rng.gen_range(1.0..1000.0)) as u32;
+            sketch.add(&item.to_le_bytes());
+            *exact.entry(item).or_default() += 1;
+            total += 1;
+        }
+        for i in 0..50_000 {
+            exact.entry(10_000 + i).or_default();
+        }
+
+        let bound = (epsilon * total as f64) as u64;
+        let mut within_bound = 0;
+        for (item, &count) in &exact {
+            let estimate = sketch.estimate(&item.to_le_bytes());
+            assert!(estimate >= count);
+            if estimate <= count + bound {
+                within_bound += 1;
+            }
+        }
+        let ratio = within_bound as f64 / exact.len() as f64;
+        assert!(
+            ratio >= 1.0 - delta,
+            "only {ratio} of estimates are within the bound"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "The digest must be at least 16 bytes long")]
+    fn digest_sketch_needs_16_byte_digests() {
+        DigestCountMinSketch::<8, crate::ciphers::Fnv1a64>::with_dimensions(10, 4);
+    }
+
+    #[test]
+    fn digest_sketch_counts_saturate() {
+        let mut sketch = DigestCountMinSketch::<32, SHA256>::with_dimensions(10, 4);
+        sketch.add_count(b"test", u64::MAX);
+        sketch.add_count(b"test", 2);
+        sketch.add(b"test");
+        assert_eq!(sketch.estimate(b"test"), u64::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "The error epsilon must be greater than zero")]
+    fn digest_sketch_rejects_zero_epsilon() {
+        DigestCountMinSketch::<32, SHA256>::new(0.0, 0.01);
+    }
+
+    #[test]
+    #[should_panic(expected = "The failure probability delta must be strictly between 0 and 1")]
+    fn digest_sketch_rejects_zero_delta() {
+        DigestCountMinSketch::<32, SHA256>::new(0.01, 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "The failure probability delta must be strictly between 0 and 1")]
+    fn digest_sketch_rejects_delta_of_one() {
+        DigestCountMinSketch::<32, SHA256>::new(0.01, 1.0);
+    }
 }
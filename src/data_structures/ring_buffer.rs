@@ -0,0 +1,157 @@
+/// A fixed-capacity circular queue. What happens when pushing to a full
+/// buffer depends on how it was created: `with_capacity` rejects the new
+/// value, while `with_capacity_overwriting` drops the oldest value instead.
+#[derive(Debug)]
+pub struct RingBuffer<T> {
+    items: Vec<Option<T>>,
+    // Index of the oldest item
+    head: usize,
+    len: usize,
+    overwrite: bool,
+}
+
+impl<T> RingBuffer<T> {
+    /// Creates an empty buffer holding at most capacity items, which refuses
+    /// pushes when full
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::new(capacity, false)
+    }
+
+    /// Creates an empty buffer holding at most capacity items, which drops
+    /// the oldest item when pushing to a full buffer
+    pub fn with_capacity_overwriting(capacity: usize) -> Self {
+        Self::new(capacity, true)
+    }
+
+    fn new(capacity: usize, overwrite: bool) -> Self {
+        if capacity == 0 {
+            panic!("Capacity must be greater than zero");
+        }
+        Self {
+            items: (0..capacity).map(|_| None).collect(),
+            head: 0,
+            len: 0,
+            overwrite,
+        }
+    }
+
+    /// Appends value as the newest item. If the buffer is full, either the
+    /// oldest item is dropped, or value is handed back as an error.
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            if !self.overwrite {
+                return Err(value);
+            }
+            self.items[self.head] = Some(value);
+            self.head = (self.head + 1) % self.capacity();
+            return Ok(());
+        }
+        let tail = (self.head + self.len) % self.capacity();
+        self.items[tail] = Some(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes and returns the oldest item
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let value = self.items[self.head].take();
+        self.head = (self.head + 1) % self.capacity();
+        self.len -= 1;
+        value
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns an iterator from the oldest to the newest item
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        (0..self.len).filter_map(move |i| self.items[(self.head + i) % self.capacity()].as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_pop_wrap_around() {
+        let mut buffer = RingBuffer::with_capacity(3);
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.pop(), None);
+        for round in 0..10 {
+            assert_eq!(buffer.push(round), Ok(()));
+            assert_eq!(buffer.push(round + 100), Ok(()));
+            assert_eq!(buffer.len(), 2);
+            assert_eq!(
+                buffer.iter().copied().collect::<Vec<_>>(),
+                vec![round, round + 100]
+            );
+            assert_eq!(buffer.pop(), Some(round));
+            assert_eq!(buffer.pop(), Some(round + 100));
+            assert!(buffer.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_full_rejects() {
+        let mut buffer = RingBuffer::with_capacity(2);
+        assert_eq!(buffer.push('a'), Ok(()));
+        assert_eq!(buffer.push('b'), Ok(()));
+        assert!(buffer.is_full());
+        assert_eq!(buffer.push('c'), Err('c'));
+        assert_eq!(buffer.iter().copied().collect::<String>(), "ab");
+        assert_eq!(buffer.pop(), Some('a'));
+        assert_eq!(buffer.push('c'), Ok(()));
+        assert_eq!(buffer.iter().copied().collect::<String>(), "bc");
+    }
+
+    #[test]
+    fn test_overwrite() {
+        let mut buffer = RingBuffer::with_capacity_overwriting(3);
+        for value in 1..=7 {
+            assert_eq!(buffer.push(value), Ok(()));
+        }
+        assert!(buffer.is_full());
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![5, 6, 7]);
+        assert_eq!(buffer.pop(), Some(5));
+        assert_eq!(buffer.push(8), Ok(()));
+        assert_eq!(buffer.push(9), Ok(()));
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![7, 8, 9]);
+    }
+
+    #[test]
+    fn test_drain() {
+        let mut buffer = RingBuffer::with_capacity_overwriting(4);
+        for value in 0..6 {
+            buffer.push(value).unwrap();
+        }
+        let drained: Vec<i32> = std::iter::from_fn(|| buffer.pop()).collect();
+        assert_eq!(drained, vec![2, 3, 4, 5]);
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.iter().next(), None);
+        assert_eq!(buffer.capacity(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "Capacity must be greater than zero")]
+    fn test_zero_capacity() {
+        RingBuffer::<i32>::with_capacity(0);
+    }
+}
@@ -1,6 +1,6 @@
 use std::cmp::min;
 use std::fmt::Debug;
-use std::ops::Range;
+use std::ops::{Add, Range};
 
 /// This data structure implements a segment-tree that can efficiently answer range (interval) queries on arrays.
 /// It represents this array as a binary tree of merged intervals. From top to bottom: [aggregated value for the overall array], then [left-hand half, right hand half], etc. until [each individual value, ...]
@@ -76,11 +76,26 @@ impl<T: Debug + Default + Ord + Copy> SegmentTree<T> {
     }
 }
 
+impl<T: Debug + Default + Ord + Copy + Add<Output = T>> SegmentTree<T> {
+    /// Builds a SegmentTree answering range-sum queries
+    pub fn from_vec_sum(arr: &[T]) -> Self {
+        Self::from_vec(arr, |a, b| a + b)
+    }
+}
+
+impl<T: Debug + Default + Ord + Copy> SegmentTree<T> {
+    /// Builds a SegmentTree answering range-min queries
+    pub fn from_vec_min(arr: &[T]) -> Self {
+        Self::from_vec(arr, min)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use quickcheck::TestResult;
     use quickcheck_macros::quickcheck;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
     use std::cmp::{max, min};
 
     #[test]
@@ -125,6 +140,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_random_against_brute_force() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut vec: Vec<i64> = (0..200).map(|_| rng.gen_range(-1000..1000)).collect();
+        let mut sum_seg_tree = SegmentTree::from_vec_sum(&vec);
+        let mut min_seg_tree = SegmentTree::from_vec_min(&vec);
+        for _ in 0..1000 {
+            if rng.gen_bool(0.5) {
+                let idx = rng.gen_range(0..vec.len());
+                let val = rng.gen_range(-1000..1000);
+                vec[idx] = val;
+                sum_seg_tree.update(idx, val);
+                min_seg_tree.update(idx, val);
+            }
+            let start = rng.gen_range(0..vec.len());
+            let end = rng.gen_range(start + 1..=vec.len());
+            assert_eq!(
+                sum_seg_tree.query(start..end),
+                Some(vec[start..end].iter().sum())
+            );
+            assert_eq!(
+                min_seg_tree.query(start..end),
+                vec[start..end].iter().min().copied()
+            );
+        }
+    }
+
     // Some properties over segment trees:
     //  When asking for the range of the overall array, return the same as iter().min() or iter().max(), etc.
     //  When asking for an interval containing a single value, return this value, no matter the merge function
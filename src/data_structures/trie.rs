@@ -52,6 +52,55 @@ where
         }
         node.value.as_ref()
     }
+
+    /// Returns true if a value was inserted under key
+    pub fn contains(&self, key: impl IntoIterator<Item = Key>) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns true if any inserted key starts with prefix
+    pub fn starts_with(&self, prefix: impl IntoIterator<Item = Key>) -> bool {
+        // Apart from the root, nodes are only created on the way to an
+        // inserted key, so every other node has a value in its subtree
+        self.find_node(prefix)
+            .is_some_and(|node| node.value.is_some() || !node.children.is_empty())
+    }
+
+    fn find_node(&self, key: impl IntoIterator<Item = Key>) -> Option<&Node<Key, Type>> {
+        let mut node = &self.root;
+        for c in key.into_iter() {
+            node = node.children.get(&c)?;
+        }
+        Some(node)
+    }
+}
+
+impl<Type> Trie<char, Type>
+where
+    Type: Default,
+{
+    /// Returns all keys starting with prefix, in lexicographic order. The
+    /// empty prefix returns every key.
+    pub fn keys_with_prefix(&self, prefix: &str) -> Vec<String> {
+        let mut keys = Vec::new();
+        if let Some(node) = self.find_node(prefix.chars()) {
+            let mut key = prefix.to_string();
+            Self::collect_keys(node, &mut key, &mut keys);
+        }
+        keys.sort();
+        keys
+    }
+
+    fn collect_keys(node: &Node<char, Type>, key: &mut String, keys: &mut Vec<String>) {
+        if node.value.is_some() {
+            keys.push(key.clone());
+        }
+        for (&c, child) in &node.children {
+            key.push(c);
+            Self::collect_keys(child, key, keys);
+            key.pop();
+        }
+    }
 }
 
 #[cfg(test)]
@@ -94,4 +143,40 @@ mod tests {
         assert_eq!(trie.get(vec![42, 6, 1000]), Some(&3));
         assert_eq!(trie.get(vec![43, 44, 45]), None);
     }
+
+    #[test]
+    fn test_prefixes() {
+        let mut trie = Trie::new();
+        for word in ["car", "card", "care", "cart", "cat", "dog", "do"] {
+            trie.insert(word.chars(), ());
+        }
+
+        assert!(trie.contains("card".chars()));
+        assert!(trie.contains("do".chars()));
+        assert!(!trie.contains("ca".chars()));
+        assert!(!trie.contains("cards".chars()));
+
+        assert!(trie.starts_with("ca".chars()));
+        assert!(trie.starts_with("card".chars()));
+        assert!(trie.starts_with("".chars()));
+        assert!(!trie.starts_with("cb".chars()));
+        assert!(!trie.starts_with("cards".chars()));
+
+        assert_eq!(
+            trie.keys_with_prefix("car"),
+            vec!["car", "card", "care", "cart"]
+        );
+        assert_eq!(trie.keys_with_prefix("do"), vec!["do", "dog"]);
+        assert_eq!(
+            trie.keys_with_prefix(""),
+            vec!["car", "card", "care", "cart", "cat", "do", "dog"]
+        );
+        assert!(trie.keys_with_prefix("x").is_empty());
+        assert!(trie.keys_with_prefix("dogs").is_empty());
+
+        let empty: Trie<char, ()> = Trie::new();
+        assert!(!empty.starts_with("a".chars()));
+        assert!(!empty.starts_with("".chars()));
+        assert!(empty.keys_with_prefix("").is_empty());
+    }
 }
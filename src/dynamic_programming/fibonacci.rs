@@ -143,12 +143,41 @@ pub fn matrix_fibonacci(n: u32) -> u128 {
     res[1][0]
 }
 
-fn matrix_power(base: &Vec<Vec<u128>>, power: u32) -> Vec<Vec<u128>> {
-    let identity_matrix: Vec<Vec<u128>> = vec![vec![1, 0], vec![0, 1]];
+// Exponentiation by squaring, so only O(log(power)) multiplications are needed
+fn matrix_power(base: &[Vec<u128>], mut power: u32) -> Vec<Vec<u128>> {
+    let mut result: Vec<Vec<u128>> = vec![vec![1, 0], vec![0, 1]];
+    let mut base = base.to_vec();
+
+    while power > 0 {
+        if power & 1 == 1 {
+            result = matrix_multiply(&result, &base);
+        }
+        power >>= 1;
+        // Squaring once more than needed could overflow for large n
+        if power > 0 {
+            base = matrix_multiply(&base, &base);
+        }
+    }
+    result
+}
 
-    vec![base; power as usize]
-        .iter()
-        .fold(identity_matrix, |acc, x| matrix_multiply(&acc, x))
+/// checked_fibonacci(n) returns the nth fibonacci number, or None if it does
+/// not fit in a 128-bit unsigned integer (n > 186)
+/// This function uses the definition of Fibonacci where:
+/// F(0) = 0, F(1) = 1 and F(n+1) = F(n) + F(n-1) for n>0
+pub fn checked_fibonacci(n: u32) -> Option<u128> {
+    if n == 0 {
+        return Some(0);
+    }
+    let mut a: u128 = 0;
+    let mut b: u128 = 1;
+    // Stop at F(n) itself, computing F(n+1) could overflow even if F(n) fits
+    for _i in 1..n {
+        let c = a.checked_add(b)?;
+        a = b;
+        b = c;
+    }
+    Some(b)
 }
 
 // Copied from matrix_ops since u128 is required instead of i32
@@ -251,6 +280,7 @@ pub fn last_digit_of_the_sum_of_nth_fibonacci_number(n: i64) -> i64 {
 
 #[cfg(test)]
 mod tests {
+    use super::checked_fibonacci;
     use super::classical_fibonacci;
     use super::fibonacci;
     use super::last_digit_of_the_sum_of_nth_fibonacci_number;
@@ -398,6 +428,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_matrix_fibonacci_near_overflow() {
+        assert_eq!(
+            matrix_fibonacci(185),
+            205697230343233228174223751303346572685
+        );
+        assert_eq!(matrix_fibonacci(185), logarithmic_fibonacci(185));
+    }
+
+    #[test]
+    fn test_checked_fibonacci() {
+        assert_eq!(checked_fibonacci(0), Some(0));
+        assert_eq!(checked_fibonacci(1), Some(1));
+        assert_eq!(checked_fibonacci(2), Some(1));
+        assert_eq!(checked_fibonacci(10), Some(55));
+        assert_eq!(
+            checked_fibonacci(186),
+            Some(332825110087067562321196029789634457848)
+        );
+        assert_eq!(checked_fibonacci(187), None);
+        assert_eq!(checked_fibonacci(u32::MAX), None);
+    }
+
+    #[test]
+    /// Check that every classical implementation agrees with each other and
+    /// with the combinatorial one shifted by one
+    fn test_implementations_agree() {
+        for n in 1..=100 {
+            let expected = fibonacci(n - 1);
+            assert_eq!(classical_fibonacci(n), expected);
+            assert_eq!(logarithmic_fibonacci(n), expected);
+            assert_eq!(memoized_fibonacci(n), expected);
+            assert_eq!(matrix_fibonacci(n), expected);
+            assert_eq!(checked_fibonacci(n), Some(expected));
+        }
+    }
+
     #[test]
     fn test_nth_fibonacci_number_modulo_m() {
         assert_eq!(nth_fibonacci_number_modulo_m(5, 10), 5);
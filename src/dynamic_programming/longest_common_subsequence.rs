@@ -5,6 +5,11 @@
 pub fn longest_common_subsequence(a: &str, b: &str) -> String {
     let a: Vec<_> = a.chars().collect();
     let b: Vec<_> = b.chars().collect();
+    lcs(&a, &b).into_iter().collect()
+}
+
+/// lcs(a, b) returns one longest common subsequence of the slices a and b.
+pub fn lcs<T: Clone + Eq>(a: &[T], b: &[T]) -> Vec<T> {
     let (na, nb) = (a.len(), b.len());
 
     // solutions[i][j] is the length of the longest common subsequence
@@ -24,12 +29,12 @@ pub fn longest_common_subsequence(a: &str, b: &str) -> String {
         }
     }
 
-    // reconstitute the solution string from the lengths
-    let mut result: Vec<char> = Vec::new();
+    // reconstitute the solution from the lengths
+    let mut result: Vec<T> = Vec::new();
     let (mut i, mut j) = (na, nb);
     while i > 0 && j > 0 {
         if a[i - 1] == b[j - 1] {
-            result.push(a[i - 1]);
+            result.push(a[i - 1].clone());
             i -= 1;
             j -= 1;
         } else if solutions[i - 1][j] > solutions[i][j - 1] {
@@ -40,12 +45,36 @@ pub fn longest_common_subsequence(a: &str, b: &str) -> String {
     }
 
     result.reverse();
-    result.iter().collect()
+    result
+}
+
+/// lcs_len(a, b) returns the length of the longest common subsequence of a
+/// and b. Only the previous row of the table is kept, and rows run along the
+/// shorter slice, so it uses O(min(a.len(), b.len())) memory.
+pub fn lcs_len<T: Eq>(a: &[T], b: &[T]) -> usize {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut row = vec![0; short.len() + 1];
+
+    for ci in long {
+        // row[j] still holds the value of the previous row, above-left is kept aside
+        let mut diagonal = 0;
+        for (j, cj) in short.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ci == cj {
+                diagonal + 1
+            } else {
+                above.max(row[j])
+            };
+            diagonal = above;
+        }
+    }
+
+    row[short.len()]
 }
 
 #[cfg(test)]
 mod tests {
-    use super::longest_common_subsequence;
+    use super::{lcs, lcs_len, longest_common_subsequence};
 
     #[test]
     fn test_longest_common_subsequence() {
@@ -70,4 +99,44 @@ mod tests {
             "世界"
         );
     }
+
+    #[test]
+    fn test_lcs_slices() {
+        let a: Vec<char> = "ABCBDAB".chars().collect();
+        let b: Vec<char> = "BDCAB".chars().collect();
+        let common = lcs(&a, &b);
+        assert_eq!(common.len(), 4);
+        assert_eq!(lcs_len(&a, &b), 4);
+        assert_eq!(lcs_len(&b, &a), 4);
+        // The result must be a subsequence of both inputs
+        for input in [&a, &b] {
+            let mut rest = input.iter();
+            assert!(common.iter().all(|c| rest.any(|x| x == c)));
+        }
+
+        assert_eq!(lcs(&[1, 5, 2, 8, 3], &[5, 0, 8, 3, 9]), vec![5, 8, 3]);
+        assert_eq!(lcs_len(&[1, 5, 2, 8, 3], &[5, 0, 8, 3, 9]), 3);
+    }
+
+    #[test]
+    fn test_lcs_empty() {
+        let empty: [u8; 0] = [];
+        assert!(lcs(&empty, &empty).is_empty());
+        assert!(lcs(&empty, &[1, 2]).is_empty());
+        assert!(lcs(&[1, 2], &empty).is_empty());
+        assert_eq!(lcs_len(&empty, &[1, 2]), 0);
+        assert_eq!(lcs_len(&[1, 2], &empty), 0);
+        assert_eq!(lcs_len(&[1, 2], &[3, 4]), 0);
+    }
+
+    #[test]
+    fn test_lcs_len_matches_lcs() {
+        let words = ["", "abcd", "acegi", "abcdgh", "aedfhr", "aggtab", "gxtxayb"];
+        for a in words {
+            for b in words {
+                let (a, b) = (a.as_bytes(), b.as_bytes());
+                assert_eq!(lcs_len(a, b), lcs(a, b).len());
+            }
+        }
+    }
 }
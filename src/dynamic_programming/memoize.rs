@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A memo table, which computes the value of each key at most once.
+///
+/// It saves writing the usual `match cache.get(&key)` boilerplate in top-down
+/// dynamic programming. Recursive functions can't borrow the table from
+/// inside the closure, so they should compute their subproblems first.
+pub struct Memoize<K, V> {
+    cache: HashMap<K, V>,
+}
+
+impl<K: Hash + Eq, V> Memoize<K, V> {
+    pub fn new() -> Self {
+        Memoize {
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Returns the cached value of key, calling f to compute it only if key
+    /// has not been seen before
+    pub fn get_or_compute(&mut self, key: K, f: impl FnOnce() -> V) -> &V {
+        self.cache.entry(key).or_insert_with(f)
+    }
+
+    /// Returns the cached value of key, without computing it
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    /// Returns the number of cached values
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+impl<K: Hash + Eq, V> Default for Memoize<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Memoize;
+    use std::cell::Cell;
+
+    #[test]
+    fn computes_once_per_key() {
+        let calls = Cell::new(0);
+        let mut memo = Memoize::new();
+        let square = |n: u64| {
+            calls.set(calls.get() + 1);
+            n * n
+        };
+
+        assert!(memo.is_empty());
+        assert_eq!(memo.get(&3), None);
+        assert_eq!(*memo.get_or_compute(3, || square(3)), 9);
+        assert_eq!(*memo.get_or_compute(3, || square(3)), 9);
+        assert_eq!(calls.get(), 1);
+
+        assert_eq!(*memo.get_or_compute(4, || square(4)), 16);
+        assert_eq!(calls.get(), 2);
+        assert_eq!(memo.len(), 2);
+        assert_eq!(memo.get(&3), Some(&9));
+    }
+
+    #[test]
+    fn returns_cached_value() {
+        let mut memo = Memoize::new();
+        memo.get_or_compute("answer", || 42);
+        // The closure is not called again, so its different result is ignored
+        assert_eq!(*memo.get_or_compute("answer", || 0), 42);
+    }
+
+    #[test]
+    fn bottom_up_subproblems() {
+        // Number of ways to climb n stairs taking 1 or 2 steps at a time
+        let mut memo: Memoize<u32, u64> = Memoize::default();
+        memo.get_or_compute(0, || 1);
+        memo.get_or_compute(1, || 1);
+        for n in 2..=50 {
+            let ways = memo.get(&(n - 1)).unwrap() + memo.get(&(n - 2)).unwrap();
+            memo.get_or_compute(n, || ways);
+        }
+        assert_eq!(memo.get(&50), Some(&20365011074));
+    }
+}
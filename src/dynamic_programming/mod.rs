@@ -11,6 +11,7 @@ mod longest_increasing_subsequence;
 mod matrix_chain_multiply;
 mod maximal_square;
 mod maximum_subarray;
+mod memoize;
 mod minimum_cost_path;
 mod rod_cutting;
 mod snail;
@@ -19,6 +20,7 @@ mod word_break;
 
 pub use self::coin_change::coin_change;
 pub use self::egg_dropping::egg_drop;
+pub use self::fibonacci::checked_fibonacci;
 pub use self::fibonacci::classical_fibonacci;
 pub use self::fibonacci::fibonacci;
 pub use self::fibonacci::last_digit_of_the_sum_of_nth_fibonacci_number;
@@ -30,13 +32,14 @@ pub use self::fibonacci::recursive_fibonacci;
 pub use self::fractional_knapsack::fractional_knapsack;
 pub use self::is_subsequence::is_subsequence;
 pub use self::knapsack::knapsack;
-pub use self::longest_common_subsequence::longest_common_subsequence;
+pub use self::longest_common_subsequence::{lcs, lcs_len, longest_common_subsequence};
 pub use self::longest_common_substring::longest_common_substring;
 pub use self::longest_continuous_increasing_subsequence::longest_continuous_increasing_subsequence;
 pub use self::longest_increasing_subsequence::longest_increasing_subsequence;
 pub use self::matrix_chain_multiply::matrix_chain_multiply;
 pub use self::maximal_square::maximal_square;
 pub use self::maximum_subarray::maximum_subarray;
+pub use self::memoize::Memoize;
 pub use self::minimum_cost_path::minimum_cost_path;
 pub use self::rod_cutting::rod_cut;
 pub use self::snail::snail;
@@ -0,0 +1,120 @@
+/// Returns a lazy iterator over all combinations of k items, generating one
+/// at a time instead of collecting all C(n, k) of them.
+///
+/// Each combination keeps the order of items. The iterator tracks the
+/// indices of the current combination, which are always strictly increasing,
+/// and steps to the next one by incrementing the rightmost index that can
+/// still move and placing the following indices right after it. The index
+/// sets therefore come in lexicographic order, starting with `0..k`.
+/// There is one combination of 0 items, and none of more than n items.
+pub fn combinations<T: Clone>(items: &[T], k: usize) -> Combinations<'_, T> {
+    Combinations {
+        items,
+        indices: (0..k).collect(),
+        done: k > items.len(),
+    }
+}
+
+/// Iterator returned by `combinations`
+pub struct Combinations<'a, T> {
+    items: &'a [T],
+    /// The indices of the next combination to yield
+    indices: Vec<usize>,
+    done: bool,
+}
+
+impl<T: Clone> Iterator for Combinations<'_, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        let combination = self
+            .indices
+            .iter()
+            .map(|&i| self.items[i].clone())
+            .collect();
+
+        // Index i can be at most n - k + i, leaving room for the ones after it
+        let (n, k) = (self.items.len(), self.indices.len());
+        let indices = &mut self.indices;
+        match (0..k).rev().find(|&i| indices[i] < n - k + i) {
+            Some(i) => {
+                indices[i] += 1;
+                for j in i + 1..k {
+                    indices[j] = indices[j - 1] + 1;
+                }
+            }
+            None => self.done = true,
+        }
+        Some(combination)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::combinations;
+
+    fn binomial(n: usize, k: usize) -> usize {
+        (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+    }
+
+    #[test]
+    fn test_small() {
+        let res: Vec<_> = combinations(&[1, 2, 3, 4], 2).collect();
+        assert_eq!(
+            res,
+            vec![
+                vec![1, 2],
+                vec![1, 3],
+                vec![1, 4],
+                vec![2, 3],
+                vec![2, 4],
+                vec![3, 4],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        let empty: [i32; 0] = [];
+        assert_eq!(combinations(&empty, 0).collect::<Vec<_>>(), vec![vec![]]);
+        assert_eq!(combinations(&empty, 1).count(), 0);
+        assert_eq!(
+            combinations(&[1, 2, 3], 0).collect::<Vec<_>>(),
+            vec![vec![]]
+        );
+        assert_eq!(
+            combinations(&[1, 2, 3], 3).collect::<Vec<_>>(),
+            vec![vec![1, 2, 3]]
+        );
+        assert_eq!(combinations(&[1, 2, 3], 4).count(), 0);
+    }
+
+    #[test]
+    fn test_counts_and_order() {
+        for n in 0..=10 {
+            // combinations of the indices themselves, to check the order
+            let items: Vec<usize> = (0..n).collect();
+            for k in 0..=n {
+                let res: Vec<_> = combinations(&items, k).collect();
+                assert_eq!(res.len(), binomial(n, k), "n = {n}, k = {k}");
+                for combination in &res {
+                    assert_eq!(combination.len(), k);
+                    assert!(combination.windows(2).all(|pair| pair[0] < pair[1]));
+                }
+                assert!(res.windows(2).all(|pair| pair[0] < pair[1]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_lazy() {
+        // C(60, 30) combinations would never finish, but the first few are cheap
+        let items: Vec<u32> = (0..60).collect();
+        let mut iter = combinations(&items, 30);
+        assert_eq!(iter.next(), Some((0..30).collect()));
+        assert_eq!(iter.next(), Some((0..29).chain([30]).collect()));
+    }
+}
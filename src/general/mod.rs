@@ -1,3 +1,4 @@
+mod combinations;
 mod convex_hull;
 mod fisher_yates_shuffle;
 mod genetic;
@@ -9,6 +10,7 @@ mod mex;
 mod permutations;
 mod two_sum;
 
+pub use self::combinations::{combinations, Combinations};
 pub use self::convex_hull::convex_hull_graham;
 pub use self::fisher_yates_shuffle::fisher_yates_shuffle;
 pub use self::genetic::GeneticAlgorithm;
@@ -20,6 +22,7 @@ pub use self::kmeans::f64::kmeans as kmeans_f64;
 pub use self::mex::mex_using_set;
 pub use self::mex::mex_using_sort;
 pub use self::permutations::{
-    heap_permute, permute, permute_unique, steinhaus_johnson_trotter_permute,
+    heap_permute, permutations, permute, permute_unique, steinhaus_johnson_trotter_permute,
+    Permutations,
 };
 pub use self::two_sum::two_sum;
@@ -0,0 +1,113 @@
+/// Returns a lazy iterator over all permutations of items, generating one at
+/// a time instead of collecting all n! of them.
+///
+/// The iterator permutes the indices of items rather than the items
+/// themselves, using the classic "next permutation" step: find the rightmost
+/// index which is smaller than its successor, swap it with the rightmost
+/// index larger than it, and reverse everything after it. The permutations
+/// therefore come in lexicographic order of their indices, which is
+/// lexicographic order of the items if they are sorted. Equal items are not
+/// merged, so n items always give n! permutations.
+pub fn permutations<T: Clone>(items: &[T]) -> Permutations<'_, T> {
+    Permutations {
+        items,
+        indices: (0..items.len()).collect(),
+        done: false,
+    }
+}
+
+/// Iterator returned by `permutations`
+pub struct Permutations<'a, T> {
+    items: &'a [T],
+    /// The indices of the next permutation to yield
+    indices: Vec<usize>,
+    done: bool,
+}
+
+impl<T: Clone> Iterator for Permutations<'_, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        let permutation = self
+            .indices
+            .iter()
+            .map(|&i| self.items[i].clone())
+            .collect();
+
+        let indices = &mut self.indices;
+        match (1..indices.len())
+            .rev()
+            .find(|&i| indices[i - 1] < indices[i])
+        {
+            Some(i) => {
+                let pivot = i - 1;
+                let successor = (i..indices.len())
+                    .rev()
+                    .find(|&j| indices[j] > indices[pivot])
+                    .unwrap();
+                indices.swap(pivot, successor);
+                indices[i..].reverse();
+            }
+            // the indices are in decreasing order, the last permutation
+            None => self.done = true,
+        }
+        Some(permutation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use crate::general::permutations::permutations;
+    use crate::general::permutations::tests::{assert_permutations, NotTooBigVec};
+
+    #[test]
+    fn test_lexicographic_order() {
+        let res: Vec<_> = permutations(&['a', 'b', 'c']).collect();
+        assert_eq!(
+            res,
+            vec![
+                vec!['a', 'b', 'c'],
+                vec!['a', 'c', 'b'],
+                vec!['b', 'a', 'c'],
+                vec!['b', 'c', 'a'],
+                vec!['c', 'a', 'b'],
+                vec!['c', 'b', 'a'],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_counts() {
+        let empty: [i32; 0] = [];
+        assert_eq!(permutations(&empty).collect::<Vec<_>>(), vec![vec![]]);
+        assert_eq!(permutations(&[1]).count(), 1);
+        for n in 2..=7 {
+            let original: Vec<usize> = (0..n).collect();
+            assert_eq!(permutations(&original).count(), (1..=n).product::<usize>());
+        }
+        // equal values are still counted separately
+        assert_eq!(permutations(&[1, 1, 1]).count(), 6);
+    }
+
+    #[test]
+    fn test_lazy() {
+        // 20! permutations would never finish, but the first few are cheap
+        let original: Vec<u32> = (0..20).collect();
+        let mut iter = permutations(&original);
+        assert_eq!(iter.next(), Some(original.clone()));
+        let mut second = original.clone();
+        second.swap(18, 19);
+        assert_eq!(iter.next(), Some(second));
+    }
+
+    #[quickcheck]
+    fn test_some_elements(NotTooBigVec { inner: original }: NotTooBigVec) {
+        let permutations: Vec<_> = permutations(&original).collect();
+        assert_permutations(&original, &permutations)
+    }
+}
@@ -1,8 +1,10 @@
 mod heap;
+mod lexicographic;
 mod naive;
 mod steinhaus_johnson_trotter;
 
 pub use self::heap::heap_permute;
+pub use self::lexicographic::{permutations, Permutations};
 pub use self::naive::{permute, permute_unique};
 pub use self::steinhaus_johnson_trotter::steinhaus_johnson_trotter_permute;
 
@@ -1,44 +1,53 @@
-use std::collections::BTreeMap;
+use num_traits::Zero;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap};
 use std::ops::Add;
 
 type Graph<V, E> = BTreeMap<V, BTreeMap<V, E>>;
 
 // performs Dijsktra's algorithm on the given graph from the given start
-// the graph is a positively-weighted undirected graph
+// the graph is a directed graph whose edge weights must not be negative, it panics otherwise
 //
 // returns a map that for each reachable vertex associates the distance and the predecessor
 // since the start has no predecessor but is reachable, map[start] will be None
 //
-// Time: O(E * logV). For each vertex, we traverse each edge, resulting in O(E). For each edge, we
-// insert a new shortest path for a vertex into the tree, resulting in O(E * logV).
-// Space: O(V). The tree holds up to V vertices.
-pub fn dijkstra<V: Ord + Copy, E: Ord + Copy + Add<Output = E>>(
+// Time: O(E * logE). Each edge can push one entry to the binary heap, and the vertex with the
+// smallest tentative distance is always popped next. Entries made stale by a shorter path are
+// skipped when they are popped.
+// Space: O(V + E) for the distances and the heap.
+pub fn dijkstra<V: Ord + Copy, E: Ord + Copy + Add<Output = E> + Zero>(
     graph: &Graph<V, E>,
     start: V,
 ) -> BTreeMap<V, Option<(V, E)>> {
     let mut ans = BTreeMap::new();
-    let mut prio = BTreeMap::new();
+    let mut dist = BTreeMap::new();
+    let mut prio = BinaryHeap::new();
 
     // start is the special case that doesn't have a predecessor
     ans.insert(start, None);
+    dist.insert(start, E::zero());
+    prio.push(Reverse((E::zero(), start)));
 
-    for (new, weight) in &graph[&start] {
-        ans.insert(*new, Some((start, *weight)));
-        prio.insert(*new, *weight);
-    }
-
-    while let Some((vertex, path_weight)) = prio.pop_first() {
+    while let Some(Reverse((path_weight, vertex))) = prio.pop() {
+        if path_weight > dist[&vertex] {
+            // a shorter path to vertex was found after this entry was pushed
+            continue;
+        }
         for (next, weight) in &graph[&vertex] {
+            assert!(
+                *weight >= E::zero(),
+                "Dijkstra's algorithm does not support negative edge weights"
+            );
             let new_weight = path_weight + *weight;
-            match ans.get(next) {
-                // if ans[next] is a lower dist than the alternative one, we do nothing
-                Some(Some((_, dist_next))) if new_weight >= *dist_next => {}
-                // if ans[next] is None then next is start and so the distance won't be changed, it won't be added again in prio
-                Some(None) => {}
-                // the new path is shorter, either new was not in ans or it was farther
+            match dist.get(next) {
+                // if dist[next] is lower or equal to the alternative one, we do nothing
+                // this also keeps start, at distance zero, without a predecessor
+                Some(dist_next) if new_weight >= *dist_next => {}
+                // the new path is shorter, either next was not reached yet or it was farther
                 _ => {
+                    dist.insert(*next, new_weight);
                     ans.insert(*next, Some((vertex, new_weight)));
-                    prio.insert(*next, new_weight);
+                    prio.push(Reverse((new_weight, *next)));
                 }
             }
         }
@@ -152,4 +161,52 @@ mod tests {
         dists_e.insert('b', Some(('c', 39)));
         assert_eq!(dijkstra(&graph, 'e'), dists_e);
     }
+
+    #[test]
+    fn shortest_path_tree() {
+        // The direct edges from 0 are all longer than going through 4
+        let mut graph = BTreeMap::new();
+        add_edge(&mut graph, 0, 1, 10);
+        add_edge(&mut graph, 0, 2, 10);
+        add_edge(&mut graph, 0, 3, 10);
+        add_edge(&mut graph, 0, 4, 1);
+        add_edge(&mut graph, 4, 3, 1);
+        add_edge(&mut graph, 3, 2, 1);
+        add_edge(&mut graph, 2, 1, 1);
+        add_edge(&mut graph, 1, 0, 1);
+        // 5 can reach the others but can't be reached
+        add_edge(&mut graph, 5, 0, 1);
+
+        let mut dists = BTreeMap::new();
+        dists.insert(0, None);
+        dists.insert(4, Some((0, 1)));
+        dists.insert(3, Some((4, 2)));
+        dists.insert(2, Some((3, 3)));
+        dists.insert(1, Some((2, 4)));
+        assert_eq!(dijkstra(&graph, 0), dists);
+        assert!(!dijkstra(&graph, 0).contains_key(&5));
+    }
+
+    #[test]
+    fn zero_weight_cycle() {
+        let mut graph = BTreeMap::new();
+        add_edge(&mut graph, 0, 1, 0);
+        add_edge(&mut graph, 1, 0, 0);
+        add_edge(&mut graph, 1, 2, 3);
+
+        let mut dists = BTreeMap::new();
+        dists.insert(0, None);
+        dists.insert(1, Some((0, 0)));
+        dists.insert(2, Some((1, 3)));
+        assert_eq!(dijkstra(&graph, 0), dists);
+    }
+
+    #[test]
+    #[should_panic(expected = "negative edge weights")]
+    fn negative_weight() {
+        let mut graph = BTreeMap::new();
+        add_edge(&mut graph, 0, 1, 2);
+        add_edge(&mut graph, 1, 2, -1);
+        dijkstra(&graph, 0);
+    }
 }
@@ -40,6 +40,10 @@ impl DisjointSetUnion {
         self.nodes[a].size += self.nodes[b].size;
         a
     }
+    // Returns true if u and v are in the same set
+    pub fn connected(&mut self, u: usize, v: usize) -> bool {
+        self.find_set(u) == self.find_set(v)
+    }
 }
 
 #[cfg(test)]
@@ -92,4 +96,43 @@ mod tests {
         }
         assert_ne!(comp_1, dsu.find_set(10));
     }
+
+    #[test]
+    fn connected_components() {
+        let mut dsu = DisjointSetUnion::new(9);
+        // Components {0, 1, 2}, {3, 4}, {5, 6, 7, 8} and {9}
+        for (u, v) in [(0, 1), (1, 2), (3, 4), (5, 6), (7, 8), (6, 8)] {
+            dsu.merge(u, v);
+        }
+        assert!(dsu.connected(0, 2));
+        assert!(dsu.connected(4, 3));
+        assert!(dsu.connected(5, 7));
+        assert!(dsu.connected(9, 9));
+        assert!(!dsu.connected(2, 3));
+        assert!(!dsu.connected(4, 8));
+        assert!(!dsu.connected(0, 9));
+
+        dsu.merge(2, 4);
+        assert!(dsu.connected(0, 3));
+        assert!(!dsu.connected(0, 5));
+        dsu.merge(9, 3);
+        assert!(dsu.connected(1, 9));
+        assert!(!dsu.connected(9, 8));
+    }
+
+    #[test]
+    fn find_set_compresses_paths() {
+        let mut dsu = DisjointSetUnion::new(7);
+        // Merging sets of equal size leaves some vertices three links away
+        // from the root
+        for (u, v) in [(0, 1), (2, 3), (4, 5), (6, 7), (0, 2), (4, 6), (0, 4)] {
+            dsu.merge(u, v);
+        }
+        let root = dsu.find_set(7);
+        for v in 0..=7 {
+            assert_eq!(dsu.find_set(v), root);
+            // After a find, every vertex points directly at the root
+            assert_eq!(dsu.nodes[v].parent, root);
+        }
+    }
 }
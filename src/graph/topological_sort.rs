@@ -120,4 +120,20 @@ mod tests {
         assert!(sort.is_err());
         assert_eq!(sort.err().unwrap(), TopoligicalSortError::CycleDetected);
     }
+
+    #[test]
+    fn test_cycle_beside_a_dag() {
+        // 1 -> 2 -> 3 is fine, but 4 and 5 depend on each other
+        let graph = vec![(1, 2), (2, 3), (3, 4), (4, 5), (5, 4)];
+        assert_eq!(
+            topological_sort(&graph),
+            Err(TopoligicalSortError::CycleDetected)
+        );
+
+        let graph = vec![(1, 2), (3, 3)];
+        assert_eq!(
+            topological_sort(&graph),
+            Err(TopoligicalSortError::CycleDetected)
+        );
+    }
 }
@@ -1,15 +1,29 @@
-fn update_step(a: &mut i32, old_a: &mut i32, quotient: i32) {
+use num_traits::{PrimInt, Signed};
+
+fn update_step<T: PrimInt>(a: &mut T, old_a: &mut T, quotient: T) {
     let temp = *a;
     *a = *old_a - quotient * temp;
     *old_a = temp;
 }
 
-pub fn extended_euclidean_algorithm(a: i32, b: i32) -> (i32, i32, i32) {
+/// Returns (gcd, x, y) where a * x + b * y = gcd, for any signed primitive
+/// integer type. The gcd is never negative. Unsigned types are not supported
+/// since the Bezout coefficients are usually negative.
+pub fn extended_euclidean_algorithm<T: PrimInt + Signed>(a: T, b: T) -> (T, T, T) {
     let (mut old_r, mut rem) = (a, b);
-    let (mut old_s, mut coeff_s) = (1, 0);
-    let (mut old_t, mut coeff_t) = (0, 1);
+    let (mut old_s, mut coeff_s) = (T::one(), T::zero());
+    let (mut old_t, mut coeff_t) = (T::zero(), T::one());
 
-    while rem != 0 {
+    while rem != T::zero() {
+        // A remainder of 1 or -1 is the gcd up to its sign. Stopping here
+        // avoids old_r / -1, which overflows when old_r is T::min_value(),
+        // and the last coefficient update, which can overflow as well
+        if rem == T::one() {
+            return (T::one(), coeff_s, coeff_t);
+        }
+        if rem == -T::one() {
+            return (T::one(), -coeff_s, -coeff_t);
+        }
         let quotient = old_r / rem;
 
         update_step(&mut rem, &mut old_r, quotient);
@@ -17,7 +31,11 @@ pub fn extended_euclidean_algorithm(a: i32, b: i32) -> (i32, i32, i32) {
         update_step(&mut coeff_t, &mut old_t, quotient);
     }
 
-    (old_r, old_s, old_t)
+    if old_r < T::zero() {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
 }
 
 #[cfg(test)]
@@ -34,4 +52,39 @@ mod tests {
         assert_eq!(extended_euclidean_algorithm(33, 44), (11, -1, 1));
         assert_eq!(extended_euclidean_algorithm(50, 70), (10, 3, -2));
     }
+
+    #[test]
+    fn zero_operands() {
+        assert_eq!(extended_euclidean_algorithm(0, 0), (0, 1, 0));
+        assert_eq!(extended_euclidean_algorithm(7, 0), (7, 1, 0));
+        assert_eq!(extended_euclidean_algorithm(0, 7), (7, 0, 1));
+        assert_eq!(extended_euclidean_algorithm(-4, 0), (4, -1, 0));
+        assert_eq!(extended_euclidean_algorithm(0, -7), (7, 0, -1));
+    }
+
+    #[test]
+    fn bezout_identity() {
+        for a in -60_i64..60 {
+            for b in -60_i64..60 {
+                let (gcd, x, y) = extended_euclidean_algorithm(a, b);
+                assert_eq!(a * x + b * y, gcd);
+                assert_eq!(gcd, crate::math::greatest_common_divisor(a, b));
+            }
+        }
+        for (a, b) in [
+            (i32::MIN, -1),
+            (-1, i32::MIN),
+            (i32::MIN, 1),
+            (1, i32::MIN),
+            (i32::MIN, i32::MAX),
+        ] {
+            let (gcd, x, y) = extended_euclidean_algorithm(a, b);
+            assert_eq!(gcd, 1);
+            assert_eq!(a as i64 * x as i64 + b as i64 * y as i64, 1);
+        }
+        let (a, b) = (1_i128 << 60, 3_i128.pow(38));
+        let (gcd, x, y) = extended_euclidean_algorithm(a, b);
+        assert_eq!(gcd, 1);
+        assert_eq!(a * x + b * y, 1);
+    }
 }
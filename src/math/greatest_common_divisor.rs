@@ -4,6 +4,7 @@
 ///
 /// Wikipedia reference: https://en.wikipedia.org/wiki/Greatest_common_divisor
 /// gcd(a, b) = gcd(a, -b) = gcd(-a, b) = gcd(-a, -b) by definition of divisibility
+use num_traits::PrimInt;
 use std::cmp::{max, min};
 
 pub fn greatest_common_divisor_recursive(a: i64, b: i64) -> i64 {
@@ -45,6 +46,25 @@ pub fn greatest_common_divisor_stein(a: u64, b: u64) -> u64 {
     }
 }
 
+/// Iterative Euclidean algorithm for any primitive integer type, signed or
+/// unsigned. The result is never negative, and gcd(0, 0) = 0.
+pub fn greatest_common_divisor<T: PrimInt>(mut a: T, mut b: T) -> T {
+    while a != T::zero() {
+        // b % a overflows for T::min_value() % -1, whose remainder is 0
+        let remainder = match b.checked_div(&a) {
+            Some(_) => b % a,
+            None => T::zero(),
+        };
+        b = a;
+        a = remainder;
+    }
+    if b < T::zero() {
+        T::zero() - b
+    } else {
+        b
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -94,6 +114,28 @@ mod tests {
         assert_eq!(greatest_common_divisor_iterative(-12, -27), 3);
     }
 
+    #[test]
+    fn generic() {
+        assert_eq!(greatest_common_divisor(27_u8, 12), 3);
+        assert_eq!(greatest_common_divisor(0_u32, 0), 0);
+        assert_eq!(greatest_common_divisor(0_u64, 7), 7);
+        assert_eq!(greatest_common_divisor(-64_i32, 32), 32);
+        assert_eq!(greatest_common_divisor(12_i64, -27), 3);
+        assert_eq!(greatest_common_divisor(u128::MAX, 5), 5);
+        assert_eq!(greatest_common_divisor(-1_i32, i32::MIN), 1);
+        assert_eq!(greatest_common_divisor(i32::MIN, -1_i32), 1);
+        assert_eq!(greatest_common_divisor(i64::MIN, -1_i64), 1);
+        assert_eq!(greatest_common_divisor(i8::MIN, 6), 2);
+        for a in -50..50 {
+            for b in -50..50 {
+                assert_eq!(
+                    greatest_common_divisor(a, b),
+                    greatest_common_divisor_iterative(a, b)
+                );
+            }
+        }
+    }
+
     #[test]
     fn mix_recursive() {
         assert_eq!(greatest_common_divisor_recursive(0, -5), 5);
@@ -1,4 +1,6 @@
 // returns the least common multiple of n numbers
+use super::greatest_common_divisor;
+use num_traits::PrimInt;
 
 pub fn lcm(nums: &[usize]) -> usize {
     if nums.len() == 1 {
@@ -9,6 +11,20 @@ pub fn lcm(nums: &[usize]) -> usize {
     a * b / gcd_of_two_numbers(a, b)
 }
 
+/// Returns the least common multiple of a and b, which is never negative. It
+/// is zero if either of them is zero.
+pub fn least_common_multiple<T: PrimInt>(a: T, b: T) -> T {
+    if a == T::zero() || b == T::zero() {
+        return T::zero();
+    }
+    let lcm = a / greatest_common_divisor(a, b) * b;
+    if lcm < T::zero() {
+        T::zero() - lcm
+    } else {
+        lcm
+    }
+}
+
 fn gcd_of_two_numbers(a: usize, b: usize) -> usize {
     if b == 0 {
         return a;
@@ -27,4 +43,15 @@ mod tests {
         assert_eq!(lcm(&[10]), 10);
         assert_eq!(lcm(&[21, 110]), 2310);
     }
+
+    #[test]
+    fn two_numbers() {
+        assert_eq!(least_common_multiple(4_u32, 6), 12);
+        assert_eq!(least_common_multiple(21_u64, 110), 2310);
+        assert_eq!(least_common_multiple(-4_i32, 6), 12);
+        assert_eq!(least_common_multiple(-3_i64, -7), 21);
+        assert_eq!(least_common_multiple(0_u8, 9), 0);
+        assert_eq!(least_common_multiple(9_i16, 0), 0);
+        assert_eq!(least_common_multiple(1_u128 << 100, 1 << 90), 1 << 100);
+    }
 }
@@ -65,6 +65,17 @@ pub fn miller_rabin(number: u64, bases: &[u64]) -> u64 {
     0
 }
 
+/// These bases make Miller-Rabin deterministic for any number < 2 ^ 64
+const DETERMINISTIC_BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Deterministic primality test, correct for every u64
+pub fn is_prime_miller_rabin(number: u64) -> bool {
+    if number < 2 {
+        return false;
+    }
+    miller_rabin(number, &DETERMINISTIC_BASES) == 0
+}
+
 pub fn big_miller_rabin(number_ref: &BigUint, bases: &[u64]) -> u64 {
     let number = number_ref.clone();
 
@@ -158,6 +169,36 @@ mod tests {
         assert_ne!(miller_rabin(3486337000477823777, &DEFAULT_BASES), 0);
     }
 
+    #[test]
+    fn deterministic() {
+        assert!(!is_prime_miller_rabin(0));
+        assert!(!is_prime_miller_rabin(1));
+        assert!(is_prime_miller_rabin(2));
+        assert!(is_prime_miller_rabin(3));
+        assert!(!is_prime_miller_rabin(4));
+        assert!(is_prime_miller_rabin(37));
+        assert!(!is_prime_miller_rabin(39));
+
+        // Mersenne primes 2^31 - 1 and 2^61 - 1
+        assert!(is_prime_miller_rabin((1 << 31) - 1));
+        assert!(is_prime_miller_rabin((1 << 61) - 1));
+        // Largest prime below 2^64
+        assert!(is_prime_miller_rabin(18446744073709551557));
+        assert!(!is_prime_miller_rabin(u64::MAX));
+
+        // Carmichael numbers fool the Fermat test but not Miller-Rabin
+        for carmichael in [561, 1105, 1729, 2465, 2821, 6601, 8911, 41041, 825265] {
+            assert!(!is_prime_miller_rabin(carmichael));
+        }
+        // Strong pseudoprime to all of the bases 2, 3, 5, 7, 11, 13, 17 and 19
+        assert!(!is_prime_miller_rabin(341550071728321));
+
+        let sieve = crate::math::sieve_of_eratosthenes(2000);
+        for n in 0..=2000 {
+            assert_eq!(is_prime_miller_rabin(n), sieve.contains(&(n as usize)));
+        }
+    }
+
     #[test]
     fn big_basic() {
         assert_eq!(big_miller_rabin(&BigUint::from(3u32), &DEFAULT_BASES), 0);
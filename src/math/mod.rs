@@ -118,7 +118,7 @@ pub use self::gaussian_error_linear_unit::gaussian_error_linear_unit;
 pub use self::gcd_of_n_numbers::gcd;
 pub use self::geometric_series::geometric_series;
 pub use self::greatest_common_divisor::{
-    greatest_common_divisor_iterative, greatest_common_divisor_recursive,
+    greatest_common_divisor, greatest_common_divisor_iterative, greatest_common_divisor_recursive,
     greatest_common_divisor_stein,
 };
 pub use self::huber_loss::huber_loss;
@@ -126,7 +126,7 @@ pub use self::interest::{compound_interest, simple_interest};
 pub use self::interpolation::{lagrange_polynomial_interpolation, linear_interpolation};
 pub use self::interquartile_range::interquartile_range;
 pub use self::karatsuba_multiplication::multiply;
-pub use self::lcm_of_n_numbers::lcm;
+pub use self::lcm_of_n_numbers::{lcm, least_common_multiple};
 pub use self::leaky_relu::leaky_relu;
 pub use self::least_square_approx::least_square_approx;
 pub use self::linear_sieve::LinearSieve;
@@ -135,8 +135,8 @@ pub use self::lucas_series::dynamic_lucas_number;
 pub use self::lucas_series::recursive_lucas_number;
 pub use self::matrix_ops::Matrix;
 pub use self::mersenne_primes::{get_mersenne_primes, is_mersenne_prime};
-pub use self::miller_rabin::{big_miller_rabin, miller_rabin};
-pub use self::modular_exponential::{mod_inverse, modular_exponential};
+pub use self::miller_rabin::{big_miller_rabin, is_prime_miller_rabin, miller_rabin};
+pub use self::modular_exponential::{mod_inverse, mod_pow, modular_exponential};
 pub use self::newton_raphson::find_root;
 pub use self::nthprime::nthprime;
 pub use self::pascal_triangle::pascal_triangle;
@@ -149,7 +149,7 @@ pub use self::prime_check::prime_check;
 pub use self::prime_factors::prime_factors;
 pub use self::prime_numbers::prime_numbers;
 pub use self::quadratic_residue::{cipolla, tonelli_shanks};
-pub use self::random::PCG32;
+pub use self::random::{Rng, PCG32};
 pub use self::relu::relu;
 pub use self::sieve_of_eratosthenes::sieve_of_eratosthenes;
 pub use self::sigmoid::sigmoid;
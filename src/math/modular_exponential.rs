@@ -1,48 +1,47 @@
-/// Calculate the greatest common divisor (GCD) of two numbers and the
-/// coefficients of Bézout's identity using the Extended Euclidean Algorithm.
+use super::extended_euclidean_algorithm;
+
+/// Find the modular multiplicative inverse of a number modulo `m` using the
+/// Extended Euclidean Algorithm.
 ///
 /// # Arguments
 ///
-/// * `a` - One of the numbers to find the GCD of
-/// * `m` - The other number to find the GCD of
+/// * `a` - The number to find the modular inverse of
+/// * `m` - The modulus
 ///
 /// # Returns
 ///
-/// A tuple (gcd, x) such that:
-/// gcd - the greatest common divisor of a and m.
-/// x - the coefficient such that `a * x` is equivalent to `gcd` modulo `m`.
-pub fn gcd_extended(a: i64, m: i64) -> (i64, i64) {
-    if a == 0 {
-        (m, 0)
-    } else {
-        let (gcd, x1) = gcd_extended(m % a, a);
-        let x = x1 - (m / a) * x1;
-        (gcd, x)
+/// The modular inverse of `a` modulo `m`, in the range `0..m`, or `None` if it
+/// does not exist (i.e., `a` and `m` are not coprime, or `m` is zero).
+pub fn mod_inverse(a: u64, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+    let (gcd, x, _) = extended_euclidean_algorithm((a % m) as i128, m as i128);
+    if gcd != 1 {
+        return None;
     }
+    // Ensure the modular inverse is positive
+    Some(x.rem_euclid(m as i128) as u64)
 }
 
-/// Find the modular multiplicative inverse of a number modulo `m`.
-///
-/// # Arguments
-///
-/// * `b` - The number to find the modular inverse of
-/// * `m` - The modulus
-///
-/// # Returns
-///
-/// The modular inverse of `b` modulo `m`.
+/// Compute `base` raised to `exp` modulo `modulus` by square-and-multiply.
+/// The intermediate products are computed on u128, so any u64 modulus works.
 ///
 /// # Panics
 ///
-/// Panics if the inverse does not exist (i.e., `b` and `m` are not coprime).
-pub fn mod_inverse(b: i64, m: i64) -> i64 {
-    let (gcd, x) = gcd_extended(b, m);
-    if gcd != 1 {
-        panic!("Inverse does not exist");
-    } else {
-        // Ensure the modular inverse is positive
-        (x % m + m) % m
+/// Panics if `modulus` is zero.
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result = 1 % modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        exp >>= 1;
+        base = base * base % modulus;
     }
+    result as u64
 }
 
 /// Perform modular exponentiation of a number raised to a power modulo `m`.
@@ -64,7 +63,8 @@ pub fn modular_exponential(base: i64, mut power: i64, modulus: i64) -> i64 {
 
     // Adjust if the exponent is negative by finding the modular inverse
     let mut base = if power < 0 {
-        mod_inverse(base, modulus)
+        let base = base.rem_euclid(modulus) as u64;
+        mod_inverse(base, modulus as u64).expect("Inverse does not exist") as i64
     } else {
         base % modulus
     };
@@ -98,22 +98,58 @@ mod tests {
 
     #[test]
     fn test_modular_exponential_negative() {
-        assert_eq!(
-            modular_exponential(7, -2, 13),
-            mod_inverse(7, 13).pow(2) % 13
-        ); // Inverse of 7 mod 13 is 2, 2^2 % 13 = 4 % 13 = 4
-        assert_eq!(
-            modular_exponential(5, -5, 31),
-            mod_inverse(5, 31).pow(5) % 31
-        ); // Inverse of 5 mod 31 is 25, 25^5 % 31 = 25
-        assert_eq!(
-            modular_exponential(10, -8, 11),
-            mod_inverse(10, 11).pow(8) % 11
-        ); // Inverse of 10 mod 11 is 10, 10^8 % 11 = 10
-        assert_eq!(
-            modular_exponential(123, -45, 67),
-            mod_inverse(123, 67).pow(45) % 67
-        ); // Inverse of 123 mod 67 is calculated via the function
+        assert_eq!(modular_exponential(7, -2, 13), 4); // Inverse of 7 mod 13 is 2, 2^2 % 13 = 4
+        assert_eq!(modular_exponential(5, -5, 31), 5); // Inverse of 5 mod 31 is 25, 25^5 % 31 = 5
+        assert_eq!(modular_exponential(10, -8, 11), 1); // Inverse of 10 mod 11 is 10, 10^8 % 11 = 1
+        assert_eq!(modular_exponential(123, -45, 67), 40); // Inverse of 123 mod 67 is 6, 6^45 % 67 = 40
+    }
+
+    #[test]
+    #[should_panic(expected = "Inverse does not exist")]
+    fn test_modular_exponential_negative_without_inverse() {
+        modular_exponential(4, -1, 8);
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3, 7), Some(5));
+        assert_eq!(mod_inverse(7, 13), Some(2));
+        assert_eq!(mod_inverse(123, 67), Some(6));
+        assert_eq!(mod_inverse(10, 1), Some(0));
+        assert_eq!(mod_inverse(4, 8), None);
+        assert_eq!(mod_inverse(0, 7), None);
+        assert_eq!(mod_inverse(3, 0), None);
+        for a in 1..97 {
+            let inverse = mod_inverse(a, 97).unwrap();
+            assert_eq!(a * inverse % 97, 1);
+        }
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(mod_pow(2, 3, 5), 3);
+        assert_eq!(mod_pow(123, 45, 67), 62);
+        assert_eq!(mod_pow(2, 0, 7), 1);
+        assert_eq!(mod_pow(2, 0, 1), 0);
+        assert_eq!(mod_pow(0, 0, 7), 1);
+        assert_eq!(mod_pow(u64::MAX, 2, u64::MAX - 1), 1);
+    }
+
+    #[test]
+    fn test_fermat() {
+        // a^(p - 1) = 1 and a^(p - 2) = a^(-1) modulo a prime p
+        for p in [
+            7,
+            97,
+            1_000_000_007,
+            2_305_843_009_213_693_951,
+            18_446_744_073_709_551_557,
+        ] {
+            for a in [2, 3, 10, 12_345, p - 1] {
+                assert_eq!(mod_pow(a, p - 1, p), 1);
+                assert_eq!(mod_inverse(a, p), Some(mod_pow(a, p - 2, p)));
+            }
+        }
     }
 
     #[test]
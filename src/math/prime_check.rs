@@ -17,6 +17,7 @@ pub fn prime_check(num: usize) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::math::sieve_of_eratosthenes;
 
     #[test]
     fn basic() {
@@ -30,4 +31,19 @@ mod tests {
         assert!(!prime_check(21));
         assert!(!prime_check(2004));
     }
+
+    #[test]
+    fn zero_and_one() {
+        assert!(!prime_check(0));
+        assert!(!prime_check(1));
+    }
+
+    #[test]
+    fn agrees_with_sieve() {
+        let limit = 5000;
+        let primes = sieve_of_eratosthenes(limit);
+        assert_eq!(&primes[..10], &[2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        let checked: Vec<usize> = (0..=limit).filter(|&n| prime_check(n)).collect();
+        assert_eq!(checked, primes);
+    }
 }
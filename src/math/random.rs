@@ -6,6 +6,8 @@ Note that this is _NOT_ intended for serious applications. Use this generator
 at your own risk and only use your own values instead of the default ones if
 you really know what you are doing.
  */
+use std::ops::Range;
+
 pub struct PCG32 {
     state: u64,
     multiplier: u64,
@@ -114,6 +116,51 @@ impl<'a> Iterator for IterMut<'a> {
     }
 }
 
+/// A source of reproducible random numbers, so that randomized algorithms and
+/// tests in this crate don't need the `rand` crate. The same seed always
+/// gives the same sequence.
+pub trait Rng {
+    /// Creates a generator whose sequence is determined by seed
+    fn from_seed(seed: u64) -> Self
+    where
+        Self: Sized;
+
+    fn next_u64(&mut self) -> u64;
+
+    /// Returns a uniformly distributed number in range.
+    ///
+    /// Taking `next_u64() % len` would favor small results whenever len does
+    /// not divide 2^64, so numbers from the incomplete last block of len
+    /// values are rejected and drawn again. At most half of all numbers are
+    /// rejected, so this takes fewer than two draws on average.
+    ///
+    /// Panics if range is empty.
+    fn gen_range(&mut self, range: Range<u64>) -> u64 {
+        if range.is_empty() {
+            panic!("Cannot sample from an empty range");
+        }
+        let len = range.end - range.start;
+        // The largest multiple of len which is at most 2^64 - 1
+        let limit = u64::MAX - u64::MAX % len;
+        loop {
+            let x = self.next_u64();
+            if x < limit {
+                return range.start + x % len;
+            }
+        }
+    }
+}
+
+impl Rng for PCG32 {
+    fn from_seed(seed: u64) -> Self {
+        PCG32::new_default(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.get_u64()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -140,4 +187,46 @@ mod tests {
         randoms.dedup();
         assert_eq!(randoms.len(), numbers);
     }
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let mut a = PCG32::from_seed(42);
+        let mut b = PCG32::from_seed(42);
+        let mut c = PCG32::from_seed(43);
+        let first: Vec<u64> = (0..100).map(|_| a.next_u64()).collect();
+        assert!(first.iter().all(|&x| x == b.next_u64()));
+        assert!(first.iter().any(|&x| x != c.next_u64()));
+        assert_eq!(
+            PCG32::from_seed(42).get_u32(),
+            PCG32::new_default(42).get_u32()
+        );
+    }
+
+    #[test]
+    fn gen_range_bounds() {
+        let mut rng = PCG32::from_seed(0);
+        let mut seen = [false; 10];
+        for _ in 0..1000 {
+            let x = rng.gen_range(5..15);
+            assert!((5..15).contains(&x));
+            seen[(x - 5) as usize] = true;
+        }
+        assert!(seen.iter().all(|&seen| seen));
+
+        for _ in 0..100 {
+            assert_eq!(rng.gen_range(7..8), 7);
+            let x = rng.gen_range(u64::MAX - 3..u64::MAX);
+            assert!((u64::MAX - 3..u64::MAX).contains(&x));
+            // more than half of all numbers, so many of them are rejected
+            let x = rng.gen_range(0..(1 << 63) + 1);
+            assert!(x <= 1 << 63);
+        }
+        assert!(rng.gen_range(0..u64::MAX) < u64::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot sample from an empty range")]
+    fn gen_range_empty() {
+        PCG32::from_seed(0).gen_range(3..3);
+    }
 }
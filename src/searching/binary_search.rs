@@ -28,6 +28,35 @@ pub fn binary_search<T: Ord>(item: &T, arr: &[T]) -> Option<usize> {
     None
 }
 
+/// Returns the index of the first element of the ascending arr which is not
+/// less than item, or `arr.len()` if there is none
+pub fn lower_bound<T: Ord>(item: &T, arr: &[T]) -> usize {
+    partition_point(arr, |x| x < item)
+}
+
+/// Returns the index of the first element of the ascending arr which is
+/// greater than item, or `arr.len()` if there is none. Together with
+/// `lower_bound` it brackets the run of elements equal to item.
+pub fn upper_bound<T: Ord>(item: &T, arr: &[T]) -> usize {
+    partition_point(arr, |x| x <= item)
+}
+
+// Index of the first element for which pred is false, assuming pred holds for
+// a prefix of arr
+fn partition_point<T>(arr: &[T], pred: impl Fn(&T) -> bool) -> usize {
+    let mut left = 0;
+    let mut right = arr.len();
+    while left < right {
+        let mid = left + (right - left) / 2;
+        if pred(&arr[mid]) {
+            left = mid + 1;
+        } else {
+            right = mid;
+        }
+    }
+    left
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -103,4 +132,34 @@ mod tests {
         let index = binary_search(&5, &[4, 3, 2, 1]);
         assert_eq!(index, None);
     }
+
+    #[test]
+    fn bounds_empty_and_one_item() {
+        assert_eq!(lower_bound(&1, &[]), 0);
+        assert_eq!(upper_bound(&1, &[]), 0);
+
+        assert_eq!(lower_bound(&0, &[1]), 0);
+        assert_eq!(upper_bound(&0, &[1]), 0);
+        assert_eq!(lower_bound(&1, &[1]), 0);
+        assert_eq!(upper_bound(&1, &[1]), 1);
+        assert_eq!(lower_bound(&2, &[1]), 1);
+        assert_eq!(upper_bound(&2, &[1]), 1);
+    }
+
+    #[test]
+    fn bounds_with_duplicates() {
+        let arr = [1, 2, 2, 2, 3, 5, 5, 8];
+        assert_eq!(lower_bound(&2, &arr), 1);
+        assert_eq!(upper_bound(&2, &arr), 4);
+        assert_eq!(lower_bound(&5, &arr), 5);
+        assert_eq!(upper_bound(&5, &arr), 7);
+        // A missing item gives an empty run at its insertion point
+        assert_eq!(lower_bound(&4, &arr), 5);
+        assert_eq!(upper_bound(&4, &arr), 5);
+        assert_eq!(lower_bound(&0, &arr), 0);
+        assert_eq!(upper_bound(&9, &arr), arr.len());
+
+        let index = binary_search(&2, &arr).unwrap();
+        assert!((lower_bound(&2, &arr)..upper_bound(&2, &arr)).contains(&index));
+    }
 }
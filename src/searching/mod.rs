@@ -15,7 +15,7 @@ mod ternary_search_min_max;
 mod ternary_search_min_max_recursive;
 mod ternary_search_recursive;
 
-pub use self::binary_search::binary_search;
+pub use self::binary_search::{binary_search, lower_bound, upper_bound};
 pub use self::binary_search_recursive::binary_search_rec;
 pub use self::exponential_search::exponential_search;
 pub use self::fibonacci_search::fibonacci_search;
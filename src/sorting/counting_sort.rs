@@ -54,6 +54,7 @@ mod test {
     use super::*;
     use crate::sorting::have_same_elements;
     use crate::sorting::is_sorted;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
 
     #[test]
     fn counting_sort_descending() {
@@ -73,6 +74,27 @@ mod test {
         assert!(is_sorted(&ve2) && have_same_elements(&ve2, &cloned));
     }
 
+    #[test]
+    fn counting_sort_empty_and_one_element() {
+        let mut empty: Vec<u32> = vec![];
+        counting_sort(&mut empty, 0);
+        assert!(empty.is_empty());
+
+        let mut one = vec![7];
+        counting_sort(&mut one, 7);
+        assert_eq!(one, vec![7]);
+    }
+
+    #[test]
+    fn counting_sort_matches_std_sort() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut ve: Vec<u32> = (0..10000).map(|_| rng.gen_range(0..=1000)).collect();
+        let mut expected = ve.clone();
+        expected.sort_unstable();
+        counting_sort(&mut ve, 1000);
+        assert_eq!(ve, expected);
+    }
+
     #[test]
     fn generic_counting_sort() {
         let mut ve1: Vec<u8> = vec![100, 30, 60, 10, 20, 120, 1];
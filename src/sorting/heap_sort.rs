@@ -137,6 +137,14 @@ mod tests {
         assert!(is_sorted(&arr) && have_same_elements(&arr, &cloned));
     }
 
+    #[test]
+    fn reverse_sorted_array() {
+        let mut arr: Vec<i32> = (0..1000).rev().collect();
+        let cloned = arr.clone();
+        heap_sort(&mut arr);
+        assert!(is_sorted(&arr) && have_same_elements(&arr, &cloned));
+    }
+
     #[test]
     fn repeated_elements() {
         let mut arr = vec![542, 542, 542, 542];
@@ -9,7 +9,8 @@ fn merge<T: Ord + Copy>(arr: &mut [T], mid: usize) {
 
     for v in arr {
         // Choose either the smaller element, or from whichever vec is not exhausted.
-        if r == right_half.len() || (l < left_half.len() && left_half[l] < right_half[r]) {
+        // Ties are taken from the left half, which keeps the sort stable.
+        if r == right_half.len() || (l < left_half.len() && left_half[l] <= right_half[r]) {
             *v = left_half[l];
             l += 1;
         } else {
@@ -57,6 +58,48 @@ pub fn bottom_up_merge_sort<T: Copy + Ord>(a: &mut [T]) {
 
 #[cfg(test)]
 mod tests {
+    use std::cmp::Ordering;
+
+    /// Compares by key only, so that the sort cannot tell apart items with
+    /// equal keys and their index reveals whether the sort is stable
+    #[derive(Clone, Copy, Debug)]
+    struct Keyed {
+        key: u8,
+        index: usize,
+    }
+
+    impl PartialEq for Keyed {
+        fn eq(&self, other: &Self) -> bool {
+            self.key == other.key
+        }
+    }
+
+    impl Eq for Keyed {}
+
+    impl PartialOrd for Keyed {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Keyed {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.key.cmp(&other.key)
+        }
+    }
+
+    fn check_stable(sort: fn(&mut [Keyed])) {
+        let mut res: Vec<Keyed> = (0..1000)
+            .map(|index| Keyed {
+                key: (index * 7919 % 13) as u8,
+                index,
+            })
+            .collect();
+        sort(&mut res);
+        for pair in res.windows(2) {
+            assert!((pair[0].key, pair[0].index) < (pair[1].key, pair[1].index));
+        }
+    }
     #[cfg(test)]
     mod top_down_merge_sort {
         use super::super::*;
@@ -110,6 +153,11 @@ mod tests {
             top_down_merge_sort(&mut res);
             assert!(is_sorted(&res) && have_same_elements(&res, &cloned));
         }
+
+        #[test]
+        fn stable() {
+            super::check_stable(top_down_merge_sort);
+        }
     }
 
     #[cfg(test)]
@@ -165,5 +213,10 @@ mod tests {
             bottom_up_merge_sort(&mut res);
             assert!(is_sorted(&res) && have_same_elements(&res, &cloned));
         }
+
+        #[test]
+        fn stable() {
+            super::check_stable(bottom_up_merge_sort);
+        }
     }
 }
@@ -25,8 +25,26 @@ pub fn partition<T: PartialOrd>(arr: &mut [T], lo: usize, hi: usize) -> usize {
     i
 }
 
+/// Moves the median of the first, middle and last elements to hi, so that
+/// `partition` uses it as the pivot. This avoids the quadratic worst case on
+/// sorted and reverse sorted input.
+fn median_of_three<T: Ord>(arr: &mut [T], lo: usize, hi: usize) {
+    let mid = lo + (hi - lo) / 2;
+    if arr[mid] < arr[lo] {
+        arr.swap(mid, lo);
+    }
+    if arr[hi] < arr[lo] {
+        arr.swap(hi, lo);
+    }
+    if arr[hi] < arr[mid] {
+        arr.swap(hi, mid);
+    }
+    arr.swap(mid, hi);
+}
+
 fn _quick_sort<T: Ord>(arr: &mut [T], lo: usize, hi: usize) {
     if lo < hi {
+        median_of_three(arr, lo, hi);
         let p = partition(arr, lo, hi);
         if p > 0 {
             _quick_sort(arr, lo, p - 1);
@@ -97,6 +115,30 @@ mod tests {
         assert!(is_sorted(&res) && have_same_elements(&res, &cloned));
     }
 
+    #[test]
+    fn large_pre_sorted() {
+        let mut res = sort_utils::generate_ordered_vec(100000);
+        let cloned = res.clone();
+        quick_sort(&mut res);
+        assert!(is_sorted(&res) && have_same_elements(&res, &cloned));
+    }
+
+    #[test]
+    fn large_reverse_sorted() {
+        let mut res = sort_utils::generate_reverse_ordered_vec(100000);
+        let cloned = res.clone();
+        quick_sort(&mut res);
+        assert!(is_sorted(&res) && have_same_elements(&res, &cloned));
+    }
+
+    #[test]
+    fn all_equal() {
+        let mut res = vec![7; 100000];
+        let cloned = res.clone();
+        quick_sort(&mut res);
+        assert!(is_sorted(&res) && have_same_elements(&res, &cloned));
+    }
+
     #[test]
     fn large_elements() {
         let mut res = sort_utils::generate_random_vec(300000, 0, 1000000);
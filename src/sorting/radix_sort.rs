@@ -10,8 +10,9 @@ pub fn radix_sort(arr: &mut [u64]) {
         Some(&x) => x as usize,
         None => return,
     };
-    // Make radix a power of 2 close to arr.len() for optimal runtime
-    let radix = arr.len().next_power_of_two();
+    // Make radix a power of 2 close to arr.len() for optimal runtime. A radix
+    // of 1 would never make progress, so it is at least 2.
+    let radix = arr.len().next_power_of_two().max(2);
     // Counting sort by each digit from least to most significant
     let mut place = 1;
     while place <= max {
@@ -30,7 +31,11 @@ pub fn radix_sort(arr: &mut [u64]) {
             counter[digit_of(x)] -= 1;
             arr[counter[digit_of(x)]] = x;
         }
-        place *= radix;
+        // Stop once the next place would not fit, there are no digits left
+        place = match place.checked_mul(radix) {
+            Some(next) => next,
+            None => break,
+        };
     }
 }
 
@@ -39,6 +44,7 @@ mod tests {
     use super::radix_sort;
     use crate::sorting::have_same_elements;
     use crate::sorting::is_sorted;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
 
     #[test]
     fn empty() {
@@ -63,4 +69,36 @@ mod tests {
         radix_sort(&mut v);
         assert!(is_sorted(&v) && have_same_elements(&v, &cloned));
     }
+
+    #[test]
+    fn one_element() {
+        let mut v = vec![42];
+        radix_sort(&mut v);
+        assert_eq!(v, vec![42]);
+    }
+
+    #[test]
+    fn largest_values() {
+        let mut v = vec![u64::MAX, 0, u64::MAX - 1, 1 << 63];
+        radix_sort(&mut v);
+        assert_eq!(v, vec![0, 1 << 63, u64::MAX - 1, u64::MAX]);
+    }
+
+    #[test]
+    fn matches_std_sort() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for len in [2, 3, 10, 257, 5000] {
+            let mut v: Vec<u64> = (0..len).map(|_| rng.gen()).collect();
+            let mut expected = v.clone();
+            expected.sort_unstable();
+            radix_sort(&mut v);
+            assert_eq!(v, expected);
+
+            let mut v: Vec<u64> = (0..len).map(|_| rng.gen_range(0..100)).collect();
+            let mut expected = v.clone();
+            expected.sort_unstable();
+            radix_sort(&mut v);
+            assert_eq!(v, expected);
+        }
+    }
 }
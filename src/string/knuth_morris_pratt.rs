@@ -1,3 +1,6 @@
+/// Returns the byte offsets in st of every occurrence of pat, including
+/// overlapping ones. An empty pattern is treated as matching nowhere, so the
+/// result is empty rather than listing every position.
 pub fn knuth_morris_pratt(st: &str, pat: &str) -> Vec<usize> {
     if st.is_empty() || pat.is_empty() {
         return vec![];
@@ -93,4 +96,22 @@ mod tests {
         let index = knuth_morris_pratt("", "abcdef");
         assert_eq!(index, vec![]);
     }
+
+    #[test]
+    fn empty_pattern() {
+        let index = knuth_morris_pratt("abcdef", "");
+        assert_eq!(index, vec![]);
+    }
+
+    #[test]
+    fn overlapping_matches() {
+        let index = knuth_morris_pratt("aaaaa", "aaa");
+        assert_eq!(index, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn pattern_longer_than_string() {
+        let index = knuth_morris_pratt("aa", "aaa");
+        assert_eq!(index, vec![]);
+    }
 }
@@ -2,7 +2,8 @@ use std::cmp::min;
 
 /// The Levenshtein distance (or edit distance) between 2 strings.\
 /// This edit distance is defined as being 1 point per insertion, substitution, or deletion which must be made to make the strings equal.
-/// This function iterates over the bytes in the string, so it may not behave entirely as expected for non-ASCII strings.
+/// This function compares Unicode scalar values (`char`s), so "é" and "e" are one substitution apart, but a letter followed
+/// by a combining accent counts as two characters.
 ///
 /// For a detailed explanation, check the example on Wikipedia: <https://en.wikipedia.org/wiki/Levenshtein_distance>\
 /// (see the examples with the matrices, for instance between KITTEN and SITTING)
@@ -19,18 +20,23 @@ use std::cmp::min;
 ///
 /// Doing this reduces space complexity from O(nm) to O(n)
 ///
-/// Second note: if we want to minimize space, since we're now O(n) make sure you use the shortest string horizontally, and the longest vertically
+/// Second note: to minimize space, since we're now O(n), the shortest string is used horizontally, and the longest vertically
 ///
 /// # Complexity
 ///   - time complexity: O(nm),
-///   - space complexity: O(n),
+///   - space complexity: O(min(n, m)),
 ///
 /// where n and m are lengths of `str_a` and `str_b`
 pub fn levenshtein_distance(string1: &str, string2: &str) -> usize {
-    if string1.is_empty() {
-        return string2.len();
+    let (string1, string2) = if string1.chars().count() <= string2.chars().count() {
+        (string1, string2)
+    } else {
+        (string2, string1)
+    };
+    let l1 = string1.chars().count();
+    if l1 == 0 {
+        return string2.chars().count();
     }
-    let l1 = string1.len();
     let mut prev_dist: Vec<usize> = (0..=l1).collect();
 
     for (row, c2) in string2.chars().enumerate() {
@@ -136,6 +142,37 @@ mod tests {
         assert_eq!(6, levenshtein_distance("Test_Case_#3", "Case #3"));
     }
 
+    #[test]
+    fn unicode_scalars() {
+        assert_eq!(0, levenshtein_distance("héllo", "héllo"));
+        assert_eq!(1, levenshtein_distance("héllo", "hello"));
+        assert_eq!(1, levenshtein_distance("", "é"));
+        assert_eq!(1, levenshtein_distance("日本語", "日本"));
+        assert_eq!(2, levenshtein_distance("日本", "日本語の"));
+        assert_eq!(3, levenshtein_distance("🦀🦀🦀", ""));
+    }
+
+    #[test]
+    fn symmetric() {
+        let words = [
+            "kitten",
+            "sitting",
+            "",
+            "a",
+            "flaw",
+            "lawn",
+            "intention",
+            "execution",
+        ];
+        for a in words {
+            for b in words {
+                assert_eq!(levenshtein_distance(a, b), levenshtein_distance(b, a));
+            }
+        }
+        assert_eq!(3, levenshtein_distance("kitten", "sitting"));
+        assert_eq!(5, levenshtein_distance("intention", "execution"));
+    }
+
     #[test]
     fn return_1_with_1_2_3() {
         assert_eq!(1, _min3(1, 2, 3));
@@ -1,13 +1,16 @@
 const MODULUS: u16 = 101;
 const BASE: u16 = 256;
 
+/// Returns the byte offsets in target of every occurrence of pattern. Windows
+/// whose rolling hash matches are compared byte by byte, so hash collisions
+/// can't produce false matches.
 pub fn rabin_karp(target: &str, pattern: &str) -> Vec<usize> {
     // Quick exit
     if target.is_empty() || pattern.is_empty() || pattern.len() > target.len() {
         return vec![];
     }
 
-    let pattern_hash = hash(pattern);
+    let pattern_hash = hash(pattern.as_bytes());
 
     // Pre-calculate BASE^(n-1)
     let mut pow_rem: u16 = 1;
@@ -20,11 +23,14 @@ pub fn rabin_karp(target: &str, pattern: &str) -> Vec<usize> {
     let mut ret = vec![];
     for i in 0..=target.len() - pattern.len() {
         rolling_hash = if i == 0 {
-            hash(&target[0..pattern.len()])
+            hash(&target.as_bytes()[0..pattern.len()])
         } else {
             recalculate_hash(target, i - 1, i + pattern.len() - 1, rolling_hash, pow_rem)
         };
-        if rolling_hash == pattern_hash && pattern[..] == target[i..i + pattern.len()] {
+        // Compare bytes, since i need not be on a char boundary of target
+        if rolling_hash == pattern_hash
+            && pattern.as_bytes() == &target.as_bytes()[i..i + pattern.len()]
+        {
             ret.push(i);
         }
     }
@@ -32,9 +38,9 @@ pub fn rabin_karp(target: &str, pattern: &str) -> Vec<usize> {
 }
 
 // hash(s) is defined as BASE^(n-1) * s_0 + BASE^(n-2) * s_1 + ... + BASE^0 * s_(n-1)
-fn hash(s: &str) -> u16 {
+fn hash(s: &[u8]) -> u16 {
     let mut res: u16 = 0;
-    for &c in s.as_bytes().iter() {
+    for &c in s.iter() {
         res = (res * BASE % MODULUS + c as u16) % MODULUS;
     }
     res
@@ -61,22 +67,36 @@ fn recalculate_hash(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn naive_search(target: &str, pattern: &str) -> Vec<usize> {
+        if pattern.is_empty() || pattern.len() > target.len() {
+            return vec![];
+        }
+        target
+            .as_bytes()
+            .windows(pattern.len())
+            .enumerate()
+            .filter(|(_, window)| *window == pattern.as_bytes())
+            .map(|(i, _)| i)
+            .collect()
+    }
 
     #[test]
     fn hi_hash() {
-        let hash_result = hash("hi");
+        let hash_result = hash(b"hi");
         assert_eq!(hash_result, 65);
     }
 
     #[test]
     fn abr_hash() {
-        let hash_result = hash("abr");
+        let hash_result = hash(b"abr");
         assert_eq!(hash_result, 4);
     }
 
     #[test]
     fn bra_hash() {
-        let hash_result = hash("bra");
+        let hash_result = hash(b"bra");
         assert_eq!(hash_result, 30);
     }
 
@@ -134,4 +154,38 @@ mod tests {
         let index = rabin_karp("", "abcdef");
         assert_eq!(index, vec![]);
     }
+
+    #[test]
+    fn hash_collision() {
+        // "aO" and "hi" have the same hash, so only the verification rejects "aO"
+        assert_eq!(hash(b"aO"), hash(b"hi"));
+        let index = rabin_karp("aOhiaO", "hi");
+        assert_eq!(index, vec![2]);
+    }
+
+    #[test]
+    fn non_ascii_target() {
+        let target = "héllo wörld, hello world";
+        assert_eq!(rabin_karp(target, "l"), naive_search(target, "l"));
+        assert_eq!(rabin_karp(target, "wörld"), vec![7]);
+        // The second byte of 'é' collides with 'D', in the middle of a char
+        assert_eq!(rabin_karp("éD", "D"), vec![2]);
+    }
+
+    #[test]
+    fn matches_naive_search() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..200 {
+            let target: String = (0..rng.gen_range(0..60))
+                .map(|_| rng.gen_range(b'a'..=b'c') as char)
+                .collect();
+            let pattern: String = (0..rng.gen_range(1..5))
+                .map(|_| rng.gen_range(b'a'..=b'c') as char)
+                .collect();
+            assert_eq!(
+                rabin_karp(&target, &pattern),
+                naive_search(&target, &pattern)
+            );
+        }
+    }
 }